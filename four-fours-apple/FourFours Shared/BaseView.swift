@@ -41,7 +41,12 @@ class BaseView: SKNode {
     self.transitionService = transitionService
   }
   
-  func setPresenter(presenter: AnyObject) {
+  /// Keep the presenter bound to this view alive.  Binding returns nil if
+  /// rust failed to create the presenter, which leaves the view without one
+  func setPresenter(presenter: AnyObject?) {
+    if presenter == nil {
+      NSLog("Failed to bind a presenter to %@", String(describing: type(of: self)))
+    }
     self.presenter = presenter
   }
   
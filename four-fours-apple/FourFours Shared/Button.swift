@@ -72,7 +72,7 @@ class Button : SKNode {
   }
   
   func removeHandler(_ handler: ClickHandler) {
-    DispatchQueue.main.sync {
+    removeOnMainThread {
       if let index = self.clickHandlers.index(of: handler) {
         self.clickHandlers.remove(at: index)
       }
@@ -28,11 +28,17 @@ class GameScene: SKScene {
   }
 
   private var currentView : BaseView?
+  private var lifecycleObservers : [NSObjectProtocol] = []
   
   func setUpScene() {
     let systemView = SystemView(textureLoader: TextureLoader())
 
-    let ctx = RustBinder.bindToRust(systemView)
+    guard let ctx = RustBinder.bindToRust(systemView) else {
+      NSLog("Failed to create the application, so nothing will be shown")
+      return
+    }
+    
+    observeLifecycle(ctx: ctx)
     
     let transitioner = TransitionService(transitionClosure: { (view) in
       DispatchQueue.main.async {
@@ -61,6 +67,32 @@ class GameScene: SKScene {
   }
   #endif
   
+  /// Forward the app's lifecycle notifications to the rust application
+  func observeLifecycle(ctx: ApplicationContext) {
+    #if os(iOS) || os(tvOS)
+    let notifications : [(Notification.Name, () -> ())] = [
+      (.UIApplicationDidEnterBackground, { ctx.enterBackground() }),
+      (.UIApplicationWillEnterForeground, { ctx.enterForeground() }),
+      (.UIApplicationDidReceiveMemoryWarning, { ctx.lowMemory() }),
+      (.UIApplicationWillTerminate, { ctx.terminate() })
+    ]
+    #elseif os(OSX)
+    let notifications : [(Notification.Name, () -> ())] = [
+      (NSApplication.didHideNotification, { ctx.enterBackground() }),
+      (NSApplication.didUnhideNotification, { ctx.enterForeground() }),
+      (NSApplication.willTerminateNotification, { ctx.terminate() })
+    ]
+    #else
+    let notifications : [(Notification.Name, () -> ())] = []
+    #endif
+    
+    lifecycleObservers.forEach { NotificationCenter.default.removeObserver($0) }
+    lifecycleObservers = notifications.map { (name, action) in
+      NotificationCenter.default.addObserver(
+          forName: name, object: nil, queue: nil) { _ in action() }
+    }
+  }
+  
   func setSize(size: CGSize) {
     let setSizeOp : () -> () = {
       self.size = size
@@ -75,6 +107,12 @@ class GameScene: SKScene {
     }
   }
   
+  #if os(OSX)
+  override func keyDown(with event: NSEvent) {
+    currentView?.keyDown(with: event)
+  }
+  #endif
+  
   override func update(_ currentTime: TimeInterval) {
     // Called before each frame is rendered
   }
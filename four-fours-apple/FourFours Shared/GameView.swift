@@ -13,11 +13,38 @@ class GameView : BaseView {
   
   var dragHandlers: [DragHandler] = []
   var layoutHandlers: [LayoutHandler] = []
+  var keyHandlers: [KeyHandler] = []
   
   static var z = 1;
+  static let TEX_FONT_NAME = "XITS Math"
+  static let GHOSTED_TEX_ALPHA : CGFloat = 0.4
+  static let HUD_FONT_FRAC : CGFloat = 0.05
+  static let HUD_MARGIN_FRAC : CGFloat = 0.02
+  
+  let texNode = SKNode()
+  
+  let goalText = TextArea()
+  let valueText = TextArea()
+  let statusText = TextArea()
+  let pauseButton = Button()
   
   override init() {
     super.init()
+    texNode.zPosition = CGFloat(Int32.max)
+    addChild(texNode)
+    
+    goalText.setAlignment(.left)
+    valueText.setAlignment(.right)
+    statusText.setAlignment(.center)
+    
+    [goalText, valueText, statusText].forEach { (hudText) in
+      hudText.zPosition = CGFloat(Int32.max)
+      addChild(hudText)
+    }
+    
+    pauseButton.setFillColor(fillColor: SKColor.lightGray)
+    pauseButton.zPosition = CGFloat(Int32.max)
+    addChild(pauseButton)
   }
   
   required init?(coder aDecoder: NSCoder) {
@@ -36,7 +63,7 @@ class GameView : BaseView {
   }
   
   func removeHandler(_ handler: LayoutHandler) {
-    DispatchQueue.main.sync {
+    removeOnMainThread {
       if let index = self.layoutHandlers.index(of: handler) {
         self.layoutHandlers.remove(at: index)
       }
@@ -54,14 +81,49 @@ class GameView : BaseView {
   }
   
   func removeHandler(_ handler: DragHandler) {
-    DispatchQueue.main.sync {
+    removeOnMainThread {
       if let index = self.dragHandlers.index(of: handler) {
         self.dragHandlers.remove(at: index)
       }
     }
   }
   
+  func addKeyHandler(_ handler: KeyHandler) -> HandlerRegistration {
+    DispatchQueue.main.sync {
+      self.keyHandlers.append(handler)
+    }
+    
+    return HandlerRegistration(deregister_callback: {
+      self.removeHandler(handler)
+    })
+  }
+  
+  func removeHandler(_ handler: KeyHandler) {
+    removeOnMainThread {
+      if let index = self.keyHandlers.index(of: handler) {
+        self.keyHandlers.remove(at: index)
+      }
+    }
+  }
+  
   override func layout(size: CGSize) {
+    let fontSize = min(size.width, size.height) * GameView.HUD_FONT_FRAC
+    let margin = min(size.width, size.height) * GameView.HUD_MARGIN_FRAC
+    
+    [goalText, valueText, statusText].forEach { (hudText) in
+      hudText.setFontSize(fontSize)
+    }
+    
+    goalText.position = CGPoint(x: margin, y: -margin)
+    valueText.position = CGPoint(x: size.width - margin, y: -margin)
+    statusText.position = CGPoint(x: size.width / 2.0, y: -margin)
+    
+    let pauseButtonSize = CGSize(width: fontSize * 4.0, height: fontSize * 2.0)
+    pauseButton.setSize(size: pauseButtonSize)
+    pauseButton.position = CGPoint(
+      x: size.width - margin - pauseButtonSize.width / 2.0,
+      y: -size.height + margin + pauseButtonSize.height / 2.0)
+    
     layoutHandlers.forEach { (handler) in
       handler.onLayout(width: Int64(size.width), height: Int64(size.height))
     }
@@ -85,6 +147,54 @@ class GameView : BaseView {
     return result
   }
   
+  func transitionToPauseView() {
+    let pauseView = PauseView()
+    pauseView.setPresenter(presenter: getContext().bindToPauseView(view: pauseView))
+    transitionTo(newView: pauseView)
+  }
+  
+  func clearTex() {
+    DispatchQueue.main.async {
+      self.texNode.removeAllChildren()
+    }
+  }
+  
+  func addTexGlyph(_ codepoint: Int64, _ left: Float64, _ baseline: Float64, _ size: Float64) {
+    guard let scalar = UnicodeScalar(UInt32(codepoint)) else {
+      return
+    }
+    
+    DispatchQueue.main.async {
+      let glyph = SKLabelNode(fontNamed: GameView.TEX_FONT_NAME)
+      glyph.text = String(Character(scalar))
+      glyph.fontSize = CGFloat(size)
+      glyph.fontColor = SKColor.black
+      glyph.horizontalAlignmentMode = .left
+      glyph.verticalAlignmentMode = .baseline
+      glyph.position = CGPoint(x: CGFloat(left), y: -CGFloat(baseline))
+      self.texNode.addChild(glyph)
+    }
+  }
+  
+  func addTexRule(_ left: Float64, _ top: Float64, _ width: Float64, _ height: Float64) {
+    DispatchQueue.main.async {
+      let rule = SKShapeNode(rect: CGRect(
+        x: CGFloat(left),
+        y: -CGFloat(top + height),
+        width: CGFloat(width),
+        height: CGFloat(height)))
+      rule.fillColor = SKColor.black
+      rule.strokeColor = SKColor.clear
+      self.texNode.addChild(rule)
+    }
+  }
+  
+  func setTexGhosted(_ ghosted: Bool) {
+    DispatchQueue.main.async {
+      self.texNode.alpha = ghosted ? GameView.GHOSTED_TEX_ALPHA : 1.0
+    }
+  }
+  
   deinit {
     print("Dropping GameView")
   }
@@ -158,6 +268,20 @@ extension GameView {
   
   #if os(OSX)
   
+  override func keyDown(with event: NSEvent) {
+    guard let characters = event.characters else {
+      return
+    }
+    
+    DispatchQueue.main.async {
+      characters.unicodeScalars.forEach { (scalar) in
+        self.keyHandlers.forEach { (handler) in
+          handler.onKey(key: Int64(scalar.value))
+        }
+      }
+    }
+  }
+  
   override func mouseDown(with event: NSEvent) {
     DispatchQueue.main.async {
       let localPoint = event.location(in: self)
@@ -24,3 +24,15 @@ public class HandlerRegistration {
     print("Dropping Handler Registration")
   }
 }
+
+/// Remove a handler on the main thread, right away if already on it, without
+/// waiting otherwise.  Rust deregisters handlers from its own threads while
+/// the main thread may be waiting on it, so waiting here could deadlock
+func removeOnMainThread(_ remove: @escaping () -> Void) {
+  if Thread.isMainThread {
+    remove()
+  }
+  else {
+    DispatchQueue.main.async(execute: remove)
+  }
+}
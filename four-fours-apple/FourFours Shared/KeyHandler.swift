@@ -0,0 +1,15 @@
+//
+//  KeyHandler.swift
+//  FourFours
+//
+//  Copyright © 2018 Rook And Pawn Industries, Inc. All rights reserved.
+//
+
+import Foundation
+
+extension KeyHandler : Equatable {
+  
+  static func ==(lhs: KeyHandler, rhs: KeyHandler) -> Bool {
+    return lhs === rhs
+  }
+}
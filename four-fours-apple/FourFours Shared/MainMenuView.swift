@@ -16,6 +16,7 @@ class MainMenuView : BaseView {
   
   
   let startNewGameButton : Button
+  let startDailyChallengeButton : Button
   
   
   override init() {
@@ -23,11 +24,16 @@ class MainMenuView : BaseView {
     let startNewGameButton = Button()
     startNewGameButton.setFillColor(fillColor: SKColor.cyan)
     
+    let startDailyChallengeButton = Button()
+    startDailyChallengeButton.setFillColor(fillColor: SKColor.orange)
+    
     self.startNewGameButton = startNewGameButton
+    self.startDailyChallengeButton = startDailyChallengeButton
     
     super.init()
     
     addChild(startNewGameButton)
+    addChild(startDailyChallengeButton)
   }
   
   required init?(coder aDecoder: NSCoder) {
@@ -51,6 +57,13 @@ class MainMenuView : BaseView {
       width: width,
       height: height))
     startNewGameButton.position = CGPoint(x: size.width / 2.0, y: -size.height / 2.0)
+    
+    startDailyChallengeButton.setSize(size: CGSize(
+      width: width,
+      height: height))
+    startDailyChallengeButton.position = CGPoint(
+      x: size.width / 2.0,
+      y: -size.height / 2.0 - height * 1.2)
   }
   
   deinit {
@@ -0,0 +1,86 @@
+//
+//  PauseView.swift
+//  FourFours
+//
+//  Copyright © 2018 Rook And Pawn Industries, Inc. All rights reserved.
+//
+
+import Foundation
+import SpriteKit
+
+class PauseView : BaseView {
+  private static let MAX_WIDTH_FRAC : CGFloat = 0.5
+  private static let HEIGHT_FRAC : CGFloat = 0.15
+  private static let BUTTON_ASPECT_RATIO : CGFloat = 1.618
+  private static let BUTTON_SPACING_FRAC : CGFloat = 1.2
+  
+  let resumeButton : Button
+  let restartButton : Button
+  let nextPuzzleButton : Button
+  let mainMenuButton : Button
+  
+  override init() {
+    
+    let resumeButton = Button()
+    resumeButton.setFillColor(fillColor: SKColor.cyan)
+    
+    let restartButton = Button()
+    restartButton.setFillColor(fillColor: SKColor.yellow)
+    
+    let nextPuzzleButton = Button()
+    nextPuzzleButton.setFillColor(fillColor: SKColor.green)
+    
+    let mainMenuButton = Button()
+    mainMenuButton.setFillColor(fillColor: SKColor.orange)
+    
+    self.resumeButton = resumeButton
+    self.restartButton = restartButton
+    self.nextPuzzleButton = nextPuzzleButton
+    self.mainMenuButton = mainMenuButton
+    
+    super.init()
+    
+    addChild(resumeButton)
+    addChild(restartButton)
+    addChild(nextPuzzleButton)
+    addChild(mainMenuButton)
+  }
+  
+  required init?(coder aDecoder: NSCoder) {
+    fatalError("init(coder:) has not been implemented")
+  }
+  
+  func transitionToGameView() {
+    let gameView = GameView()
+    gameView.setPresenter(presenter: getContext().bindToGameView(view: gameView))
+    transitionTo(newView: gameView)
+  }
+  
+  func transitionToMainMenuView() {
+    let mainMenuView = MainMenuView()
+    mainMenuView.setPresenter(presenter: getContext().bindToMainMenuView(view: mainMenuView))
+    transitionTo(newView: mainMenuView)
+  }
+  
+  override func layout(size: CGSize) {
+    let maxHeight = size.height * PauseView.HEIGHT_FRAC
+    let maxWidth = size.width * PauseView.MAX_WIDTH_FRAC
+    
+    let width = min(maxWidth, maxHeight * PauseView.BUTTON_ASPECT_RATIO)
+    let height = width / PauseView.BUTTON_ASPECT_RATIO
+    let buttons = [resumeButton, restartButton, nextPuzzleButton, mainMenuButton]
+    let totalHeight = height * PauseView.BUTTON_SPACING_FRAC * CGFloat(buttons.count - 1)
+    
+    buttons.enumerated().forEach { (i, button) in
+      button.setSize(size: CGSize(width: width, height: height))
+      button.position = CGPoint(
+        x: size.width / 2.0,
+        y: -(size.height - totalHeight) / 2.0
+          - height * PauseView.BUTTON_SPACING_FRAC * CGFloat(i))
+    }
+  }
+  
+  deinit {
+    print("Dropping PauseView")
+  }
+}
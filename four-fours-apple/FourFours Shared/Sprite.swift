@@ -12,6 +12,7 @@ import SpriteKit
 class Sprite : SKSpriteNode {
   
   var dragHandlers: [DragHandler] = []
+  var clickHandlers: [ClickHandler] = []
   
   var currentTexture: Texture?
   var eventSink: Sprite?
@@ -43,6 +44,23 @@ class Sprite : SKSpriteNode {
     }
   }
   
+  func setTint(_ red: Float64, _ green: Float64, _ blue: Float64, _ blendFactor: Float64) {
+    DispatchQueue.main.async {
+      self.color = SKColor(
+          red: CGFloat(red),
+          green: CGFloat(green),
+          blue: CGFloat(blue),
+          alpha: 1.0)
+      self.colorBlendFactor = CGFloat(blendFactor)
+    }
+  }
+  
+  func setAlpha(_ alpha: Float64) {
+    DispatchQueue.main.async {
+      self.alpha = CGFloat(alpha)
+    }
+  }
+  
   func setSizeAnimated(_ width: Float64, _ height: Float64, _ durationSeconds: Float64) {
     let resize = SKAction.resize(
         toWidth: CGFloat(width),
@@ -79,13 +97,37 @@ class Sprite : SKSpriteNode {
   }
   
   func removeHandler(_ handler: DragHandler) {
-    DispatchQueue.main.sync {
+    removeOnMainThread {
       if let index = self.dragHandlers.index(of: handler) {
         self.dragHandlers.remove(at: index)
       }
     }
   }
   
+  func addClickHandler(_ handler: ClickHandler) -> HandlerRegistration {
+    DispatchQueue.main.sync {
+      self.isUserInteractionEnabled = true
+      self.clickHandlers.append(handler)
+    }
+    return HandlerRegistration(deregister_callback: {
+      self.removeHandler(handler)
+    })
+  }
+  
+  func removeHandler(_ handler: ClickHandler) {
+    removeOnMainThread {
+      if let index = self.clickHandlers.index(of: handler) {
+        self.clickHandlers.remove(at: index)
+      }
+    }
+  }
+  
+  /// Sprites that only listen for clicks still let presses through to the
+  /// view behind them so the view can track drags
+  private var forwardsToParent: Bool {
+    return !clickHandlers.isEmpty && dragHandlers.isEmpty
+  }
+  
   func propagateEventsTo(_ sprite: Sprite) {
     DispatchQueue.main.sync {
       self.isUserInteractionEnabled = true
@@ -112,6 +154,10 @@ extension Sprite {
     DispatchQueue.main.async {
       self.eventSink?.touchesBegan(touches, with: event)
       
+      if self.forwardsToParent {
+        self.parent?.touchesBegan(touches, with: event)
+      }
+      
       let firstTouch = touches.first!
       
       let localPoint = firstTouch.location(in: self)
@@ -132,6 +178,10 @@ extension Sprite {
     DispatchQueue.main.async {
       self.eventSink?.touchesMoved(touches, with: event)
       
+      if self.forwardsToParent {
+        self.parent?.touchesMoved(touches, with: event)
+      }
+      
       let firstTouch = touches.first!
       
       let localPoint = firstTouch.location(in: self)
@@ -152,6 +202,14 @@ extension Sprite {
     DispatchQueue.main.async {
       self.eventSink?.touchesEnded(touches, with: event)
       
+      if self.forwardsToParent {
+        self.parent?.touchesEnded(touches, with: event)
+      }
+      
+      self.clickHandlers.forEach { (handler) in
+        handler.onClick()
+      }
+      
       let firstTouch = touches.first!
       
       let localPoint = firstTouch.location(in: self)
@@ -175,6 +233,11 @@ extension Sprite {
     DispatchQueue.main.async {
       self.eventSink?.mouseDown(with: event)
       
+      if self.forwardsToParent {
+        self.parent?.mouseDown(with: event)
+      }
+      
+      
       let localPoint = event.location(in: self)
       
       self.dragHandlers.forEach { (handler) in
@@ -191,6 +254,11 @@ extension Sprite {
   override func mouseDragged(with event: NSEvent) {
     DispatchQueue.main.async {
       self.eventSink?.mouseDragged(with: event)
+      
+      if self.forwardsToParent {
+        self.parent?.mouseDragged(with: event)
+      }
+      
       let localPoint = event.location(in: self)
       self.dragHandlers.forEach { (handler) in
         handler.onDragMove(
@@ -206,6 +274,15 @@ extension Sprite {
   override func mouseUp(with event: NSEvent) {
     DispatchQueue.main.async {
       self.eventSink?.mouseUp(with: event)
+      
+      if self.forwardsToParent {
+        self.parent?.mouseUp(with: event)
+      }
+      
+      self.clickHandlers.forEach { (handler) in
+        handler.onClick()
+      }
+      
       let localPoint = event.location(in: self)
       self.dragHandlers.forEach { (handler) in
         handler.onDragEnd(
@@ -9,9 +9,19 @@
 import Foundation
 
 class SystemView {
+  private static let PROGRESS_KEY = "playerProgress"
+  
   let textureLoader : TextureLoader
   
   init(textureLoader : TextureLoader) {
     self.textureLoader = textureLoader
   }
+  
+  func loadProgress() -> String {
+    return UserDefaults.standard.string(forKey: SystemView.PROGRESS_KEY) ?? ""
+  }
+  
+  func saveProgress(_ progress: String) {
+    UserDefaults.standard.set(progress, forKey: SystemView.PROGRESS_KEY)
+  }
 }
@@ -7,7 +7,43 @@
 //
 
 import Foundation
+import SpriteKit
 
-class TextArea {
-  var text: String = ""
+class TextArea : SKNode {
+  
+  let labelNode = SKLabelNode()
+  
+  private var currentText: String = ""
+  
+  var text: String {
+    get {
+      return currentText
+    }
+    set {
+      currentText = newValue
+      DispatchQueue.main.async {
+        self.labelNode.text = newValue
+      }
+    }
+  }
+  
+  override init() {
+    super.init()
+    labelNode.fontName = "Indira K"
+    labelNode.fontColor = SKColor.darkGray
+    labelNode.verticalAlignmentMode = SKLabelVerticalAlignmentMode.top
+    addChild(labelNode)
+  }
+  
+  required init?(coder aDecoder: NSCoder) {
+    fatalError("init(coder:) has not been implemented")
+  }
+  
+  func setFontSize(_ fontSize: CGFloat) {
+    labelNode.fontSize = fontSize
+  }
+  
+  func setAlignment(_ alignment: SKLabelHorizontalAlignmentMode) {
+    labelNode.horizontalAlignmentMode = alignment
+  }
 }
@@ -6,6 +6,7 @@ lazy_static! {
           .name("Long")
           .rust_name("i64")
           .swift_name("Int64")
+          .rust_default("0")
           .build().unwrap()
       );
 
@@ -14,6 +15,7 @@ lazy_static! {
           .name("Double")
           .rust_name("f64")
           .swift_name("Float64")
+          .rust_default("0.0")
           .build().unwrap()
       );
 
@@ -22,6 +24,7 @@ lazy_static! {
           .name("Boolean")
           .rust_name("bool")
           .swift_name("Bool")
+          .rust_default("false")
           .build().unwrap()
       );
 
@@ -30,6 +33,7 @@ lazy_static! {
           .name("MutableBytePointer")
           .rust_name("*mut u8")
           .swift_name("UnsafeMutablePointer<UInt8>?")
+          .rust_default("::std::ptr::null_mut()")
           .build().unwrap()
       );
 
@@ -54,7 +58,9 @@ pub enum DataType {
 pub struct PrimitiveDataType {
   pub name: &'static str,
   pub rust_name: &'static str,
-  pub swift_name: &'static str
+  pub swift_name: &'static str,
+  /// Value returned over FFI in place of one that couldn't be computed
+  pub rust_default: &'static str
 }
 
 #[derive(Serialize,Builder,Default,Clone,Copy)]
@@ -12,7 +12,13 @@ pub struct MethodDef {
   pub impl_block: Option<ImplBlockDef>,
   pub return_type: Option<DataType>,
   pub arguments: Vec<ArgumentDef>,
-  pub custom_rust_code: Option<&'static str>
+  pub custom_rust_code: Option<&'static str>,
+  /// Let panics in a rust owned method unwind into swift instead of
+  /// catching them at the FFI boundary
+  pub allow_unwind: bool,
+  /// Returned to swift when a rust owned method panics, instead of the
+  /// return type's default
+  pub panic_return_value: Option<&'static str>
 }
 
 impl MethodDef {
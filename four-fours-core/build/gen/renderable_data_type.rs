@@ -23,6 +23,14 @@ pub struct RenderableDataType {
   pub rust_type_coersion_prefix_outgoing: String,
   pub rust_type_coersion_postfix_outgoing: String,
 
+  /// Returned to swift when a call panics or is made on a null pointer
+  /// instead of producing a value.  Rust structs default to null, which
+  /// swift gets as nil
+  pub rust_default_outgoing: String,
+  /// Swift gets an optional when this is returned from rust, which is nil
+  /// when rust returned a null pointer because the call failed
+  pub null_on_failure: bool,
+
   pub swift_name_internal: String,
   pub swift_name_incoming: String,
   pub swift_name_outgoing: String,
@@ -51,6 +59,8 @@ impl RenderableDataType {
             .rust_type_coersion_postfix_incoming(String::from(").to_string()"))
             .rust_type_coersion_prefix_outgoing(String::from("Box::into_raw(Box::new(RustString::new("))
             .rust_type_coersion_postfix_outgoing(String::from(")))"))
+            .rust_default_outgoing(String::from(
+                "Box::into_raw(Box::new(RustString::new(String::new())))"))
             .swift_name_internal(String::from("String"))
             .swift_name_incoming(String::from("OpaquePointer?"))
             .swift_name_outgoing(String::from("OpaquePointer?"))
@@ -65,6 +75,7 @@ impl RenderableDataType {
             .rust_name_internal(String::from(primitive_type.rust_name))
             .rust_name_incoming(String::from(primitive_type.rust_name))
             .rust_name_outgoing(String::from(primitive_type.rust_name))
+            .rust_default_outgoing(String::from(primitive_type.rust_default))
             .swift_name_internal(String::from(primitive_type.swift_name))
             .swift_name_incoming(String::from(primitive_type.swift_name))
             .swift_name_outgoing(String::from(primitive_type.swift_name))
@@ -110,6 +121,8 @@ fn render_rust_struct_type(struct_type: &RustStructDataType
       .rust_type_coersion_postfix_incoming(String::from(" }"))
       .rust_type_coersion_prefix_outgoing(String::from("Box::into_raw(Box::new("))
       .rust_type_coersion_postfix_outgoing(String::from("))"))
+      .rust_default_outgoing(String::from("::std::ptr::null_mut()"))
+      .null_on_failure(true)
 
       .swift_name_internal(String::from(struct_type.name))
       .swift_name_incoming(String::from("OpaquePointer?"))
@@ -136,6 +149,7 @@ fn render_swift_struct_type(struct_type: &SwiftStructDataType
       .rust_type_coersion_postfix_incoming(String::from(")"))
       .rust_type_coersion_prefix_outgoing(String::from(""))
       .rust_type_coersion_postfix_outgoing(String::from(".0"))
+      .rust_default_outgoing(String::from("::std::ptr::null_mut()"))
 
       .swift_name_internal(String::from(struct_type.name))
       .swift_name_incoming(String::from("OpaquePointer?"))
@@ -16,5 +16,7 @@ pub struct RenderableFunction {
   pub require_mutable_self: bool,
   pub return_type: Option<RenderableDataType>,
   pub arguments: Vec<RenderableArgument>,
-  pub custom_rust_code: Option<String>
+  pub custom_rust_code: Option<String>,
+  pub allow_unwind: bool,
+  pub panic_return_value: Option<String>
 }
\ No newline at end of file
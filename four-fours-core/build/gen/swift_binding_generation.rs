@@ -38,12 +38,23 @@ lazy_static!{
 
     WrappedTypeDefBuilder::default()
         .wrapper_name("WrappedGamePresenter")
-        .wrapped_type_name("Arc<GamePresenter<GameView,SystemView>>")
+        .wrapped_type_name(
+            "Arc<DeferredPresenter<GamePresenter<GameView,SystemView>>>")
         .wrapped_type_imports(vec![
             "std::sync::Arc",
+            "presenter::DeferredPresenter",
             "presenter::GamePresenter"
         ])
         .build().unwrap(),
+
+    WrappedTypeDefBuilder::default()
+        .wrapper_name("WrappedPausePresenter")
+        .wrapped_type_name("Arc<PausePresenter<PauseView>>")
+        .wrapped_type_imports(vec![
+            "std::sync::Arc",
+            "presenter::PausePresenter"
+        ])
+        .build().unwrap(),
   ];
 
   #[derive(Serialize)]
@@ -132,6 +143,76 @@ lazy_static!{
                   "WrappedGamePresenter", None)))
               .build().unwrap(),
 
+          MethodDefBuilder::default()
+              .name("bind_to_pause_view")
+              .arguments(vec![
+                ArgumentDefBuilder::default()
+                    .name("view")
+                    .data_type(DataType::swift_struct(
+                        "PauseView", None))
+                    .build().unwrap()
+              ])
+              .return_type(Some(DataType::rust_struct(
+                  "WrappedPausePresenter", None)))
+              .build().unwrap(),
+
+          MethodDefBuilder::default()
+              .name("start_recording")
+              .build().unwrap(),
+
+          MethodDefBuilder::default()
+              .name("save_recording")
+              .arguments(vec![
+                ArgumentDefBuilder::default()
+                    .name("path")
+                    .data_type(STRING.clone())
+                    .build().unwrap()
+              ])
+              .return_type(Some(BOOLEAN.clone()))
+              .build().unwrap(),
+
+          MethodDefBuilder::default()
+              .name("replay_recording")
+              .arguments(vec![
+                ArgumentDefBuilder::default()
+                    .name("path")
+                    .data_type(STRING.clone())
+                    .build().unwrap()
+              ])
+              .return_type(Some(BOOLEAN.clone()))
+              .build().unwrap(),
+
+          MethodDefBuilder::default()
+              .name("start_event_trace")
+              .build().unwrap(),
+
+          MethodDefBuilder::default()
+              .name("save_event_trace")
+              .arguments(vec![
+                ArgumentDefBuilder::default()
+                    .name("path")
+                    .data_type(STRING.clone())
+                    .build().unwrap()
+              ])
+              .return_type(Some(BOOLEAN.clone()))
+              .build().unwrap(),
+
+          MethodDefBuilder::default()
+              .name("enter_background")
+              .build().unwrap(),
+
+          MethodDefBuilder::default()
+              .name("enter_foreground")
+              .build().unwrap(),
+
+          MethodDefBuilder::default()
+              .name("low_memory")
+              .build().unwrap(),
+
+          MethodDefBuilder::default()
+              .name("terminate")
+              .build().unwrap(),
+
         ])
         .build().unwrap(),
 
@@ -271,6 +352,23 @@ lazy_static!{
         ])
         .build().unwrap(),
 
+    TypeDefBuilder::default()
+        .name("KeyHandler")
+        .rust_import(Some("ui::KeyHandler"))
+        .rust_owned(true)
+        .methods(vec![
+            MethodDefBuilder::default()
+                .name("on_key")
+                .arguments(vec![
+                  ArgumentDefBuilder::default()
+                      .name("key")
+                      .data_type(LONG.clone())
+                      .build().unwrap()
+                ])
+                .build().unwrap()
+        ])
+        .build().unwrap(),
+
     TypeDefBuilder::default()
         .name("Button")
         .rust_owned(false)
@@ -515,10 +613,38 @@ lazy_static!{
             ImplDefBuilder::default()
                 .trait_name("HasMutableVisibility")
                 .trait_import(Some("ui::HasMutableVisibility"))
+                .build().unwrap(),
+
+            ImplDefBuilder::default()
+                .trait_name("HasClickHandlers")
+                .trait_import(Some("ui::HasClickHandlers"))
+                .generics(vec![
+                  GenericDefBuilder::default()
+                      .symbol(Some("R"))
+                      .bound_type("HandlerRegistration")
+                      .build().unwrap()
+                ])
                 .build().unwrap()
         ])
         .methods(vec![
 
+            MethodDefBuilder::default()
+                .name("add_click_handler")
+                .impl_block(Some(ImplBlockDefBuilder::default()
+                    .trait_name("HasClickHandlers")
+                    .build().unwrap()))
+                .arguments(vec![
+                    ArgumentDefBuilder::default()
+                        .name("click_handler")
+                        .data_type(DataType::rust_struct(
+                            "ClickHandler",
+                            Some("ui::ClickHandler")))
+                        .build().unwrap()
+                ])
+                .return_type(Some(DataType::swift_generic(Some("R"),
+                    DataType::swift_struct("HandlerRegistration", None))))
+                .build().unwrap(),
+
             MethodDefBuilder::default()
                 .name("add_drag_handler")
                 .impl_block(Some(ImplBlockDefBuilder::default()
@@ -550,6 +676,47 @@ lazy_static!{
                   .build().unwrap()))
               .build().unwrap(),
 
+          MethodDefBuilder::default()
+              .name("set_tint")
+              .arguments(vec![
+                ArgumentDefBuilder::default()
+                    .name("red")
+                    .data_type(DOUBLE.clone())
+                    .build().unwrap(),
+
+                ArgumentDefBuilder::default()
+                    .name("green")
+                    .data_type(DOUBLE.clone())
+                    .build().unwrap(),
+
+                ArgumentDefBuilder::default()
+                    .name("blue")
+                    .data_type(DOUBLE.clone())
+                    .build().unwrap(),
+
+                ArgumentDefBuilder::default()
+                    .name("blend_factor")
+                    .data_type(DOUBLE.clone())
+                    .build().unwrap()
+              ])
+              .impl_block(Some(ImplBlockDefBuilder::default()
+                  .trait_name("ui::Sprite")
+                  .build().unwrap()))
+              .build().unwrap(),
+
+          MethodDefBuilder::default()
+              .name("set_alpha")
+              .arguments(vec![
+                ArgumentDefBuilder::default()
+                    .name("alpha")
+                    .data_type(DOUBLE.clone())
+                    .build().unwrap()
+              ])
+              .impl_block(Some(ImplBlockDefBuilder::default()
+                  .trait_name("ui::Sprite")
+                  .build().unwrap()))
+              .build().unwrap(),
+
           MethodDefBuilder::default()
               .name("propagate_events_to")
               .arguments(vec![
@@ -690,6 +857,15 @@ lazy_static!{
         .fields(vec![
             FieldDefBuilder::default()
                 .name("start_new_game_button")
+                .getter_impl(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::MainMenuView")
+                    .build().unwrap()))
+                .data_type(DataType::swift_generic(Some("B"),
+                    DataType::swift_struct("Button", None)))
+                .build().unwrap(),
+
+            FieldDefBuilder::default()
+                .name("start_daily_challenge_button")
                 .getter_impl(Some(ImplBlockDefBuilder::default()
                     .trait_name("ui::MainMenuView")
                     .build().unwrap()))
@@ -713,6 +889,16 @@ lazy_static!{
         .impls(vec![
             ImplDefBuilder::default()
                 .trait_name("ui::GameView")
+                .generics(vec![
+                    GenericDefBuilder::default()
+                        .symbol(Some("TA"))
+                        .bound_type("TextArea")
+                        .build().unwrap(),
+                    GenericDefBuilder::default()
+                        .symbol(Some("B"))
+                        .bound_type("Button")
+                        .build().unwrap()
+                ])
                 .build().unwrap(),
             ImplDefBuilder::default()
                 .trait_name("ui::SpriteSource")
@@ -747,19 +933,64 @@ lazy_static!{
                         .bound_type("HandlerRegistration")
                         .build().unwrap()
                 ])
+                .build().unwrap(),
+            ImplDefBuilder::default()
+                .trait_name("HasKeyHandlers")
+                .trait_import(Some("ui::HasKeyHandlers"))
+                .generics(vec![
+                    GenericDefBuilder::default()
+                        .symbol(Some("R"))
+                        .bound_type("HandlerRegistration")
+                        .build().unwrap()
+                ])
+                .build().unwrap(),
+            ImplDefBuilder::default()
+                .trait_name("HasTexDisplay")
+                .trait_import(Some("ui::HasTexDisplay"))
                 .build().unwrap()
         ])
         .fields(vec![
-            // FieldDefBuilder::default()
-            //     .name("start_new_game_button")
-            //     .getter_impl(Some(ImplBlockDefBuilder::default()
-            //         .trait_name("ui::MainMenuView")
-            //         .build().unwrap()))
-            //     .data_type(DataType::swift_generic(Some("B"),
-            //         DataType::swift_struct("Button", None)))
-            //     .build().unwrap()
+            FieldDefBuilder::default()
+                .name("goal_text")
+                .getter_impl(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::GameView")
+                    .build().unwrap()))
+                .data_type(DataType::swift_generic(Some("TA"),
+                    DataType::swift_struct("TextArea", None)))
+                .build().unwrap(),
+            FieldDefBuilder::default()
+                .name("value_text")
+                .getter_impl(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::GameView")
+                    .build().unwrap()))
+                .data_type(DataType::swift_generic(Some("TA"),
+                    DataType::swift_struct("TextArea", None)))
+                .build().unwrap(),
+            FieldDefBuilder::default()
+                .name("status_text")
+                .getter_impl(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::GameView")
+                    .build().unwrap()))
+                .data_type(DataType::swift_generic(Some("TA"),
+                    DataType::swift_struct("TextArea", None)))
+                .build().unwrap(),
+            FieldDefBuilder::default()
+                .name("pause_button")
+                .getter_impl(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::GameView")
+                    .build().unwrap()))
+                .data_type(DataType::swift_generic(Some("B"),
+                    DataType::swift_struct("Button", None)))
+                .build().unwrap()
         ])
         .methods(vec![
+            MethodDefBuilder::default()
+                .name("transition_to_pause_view")
+                .impl_block(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::GameView")
+                    .build().unwrap()))
+                .build().unwrap(),
+
             MethodDefBuilder::default()
                 .name("add_drag_handler")
                 .impl_block(Some(ImplBlockDefBuilder::default()
@@ -777,6 +1008,23 @@ lazy_static!{
                     DataType::swift_struct("HandlerRegistration", None))))
                 .build().unwrap(),
 
+            MethodDefBuilder::default()
+                .name("add_key_handler")
+                .impl_block(Some(ImplBlockDefBuilder::default()
+                    .trait_name("HasKeyHandlers")
+                    .build().unwrap()))
+                .arguments(vec![
+                    ArgumentDefBuilder::default()
+                        .name("key_handler")
+                        .data_type(DataType::rust_struct(
+                            "KeyHandler",
+                            Some("ui::KeyHandler")))
+                        .build().unwrap()
+                ])
+                .return_type(Some(DataType::swift_generic(Some("R"),
+                    DataType::swift_struct("HandlerRegistration", None))))
+                .build().unwrap(),
+
             MethodDefBuilder::default()
                 .name("add_layout_handler")
                 .impl_block(Some(ImplBlockDefBuilder::default()
@@ -801,6 +1049,145 @@ lazy_static!{
                     .build().unwrap()))
                 .return_type(Some(DataType::swift_generic(Some("S"),
                     DataType::swift_struct("Sprite", None))))
+                .build().unwrap(),
+
+            MethodDefBuilder::default()
+                .name("clear_tex")
+                .impl_block(Some(ImplBlockDefBuilder::default()
+                    .trait_name("HasTexDisplay")
+                    .build().unwrap()))
+                .build().unwrap(),
+
+            MethodDefBuilder::default()
+                .name("add_tex_glyph")
+                .impl_block(Some(ImplBlockDefBuilder::default()
+                    .trait_name("HasTexDisplay")
+                    .build().unwrap()))
+                .arguments(vec![
+                    ArgumentDefBuilder::default()
+                        .name("codepoint")
+                        .data_type(LONG.clone())
+                        .build().unwrap(),
+                    ArgumentDefBuilder::default()
+                        .name("left")
+                        .data_type(DOUBLE.clone())
+                        .build().unwrap(),
+                    ArgumentDefBuilder::default()
+                        .name("baseline")
+                        .data_type(DOUBLE.clone())
+                        .build().unwrap(),
+                    ArgumentDefBuilder::default()
+                        .name("size")
+                        .data_type(DOUBLE.clone())
+                        .build().unwrap()
+                ])
+                .build().unwrap(),
+
+            MethodDefBuilder::default()
+                .name("add_tex_rule")
+                .impl_block(Some(ImplBlockDefBuilder::default()
+                    .trait_name("HasTexDisplay")
+                    .build().unwrap()))
+                .arguments(vec![
+                    ArgumentDefBuilder::default()
+                        .name("left")
+                        .data_type(DOUBLE.clone())
+                        .build().unwrap(),
+                    ArgumentDefBuilder::default()
+                        .name("top")
+                        .data_type(DOUBLE.clone())
+                        .build().unwrap(),
+                    ArgumentDefBuilder::default()
+                        .name("width")
+                        .data_type(DOUBLE.clone())
+                        .build().unwrap(),
+                    ArgumentDefBuilder::default()
+                        .name("height")
+                        .data_type(DOUBLE.clone())
+                        .build().unwrap()
+                ])
+                .build().unwrap(),
+
+            MethodDefBuilder::default()
+                .name("set_tex_ghosted")
+                .impl_block(Some(ImplBlockDefBuilder::default()
+                    .trait_name("HasTexDisplay")
+                    .build().unwrap()))
+                .arguments(vec![
+                    ArgumentDefBuilder::default()
+                        .name("ghosted")
+                        .data_type(BOOLEAN.clone())
+                        .build().unwrap()
+                ])
+                .build().unwrap()
+        ])
+        .build().unwrap(),
+
+    TypeDefBuilder::default()
+        .name("PauseView")
+        .rust_owned(false)
+        .impls(vec![
+            ImplDefBuilder::default()
+                .trait_name("ui::PauseView")
+                .trait_import(Some("ui"))
+                .generics(vec![
+                    GenericDefBuilder::default()
+                        .symbol(Some("B"))
+                        .bound_type("Button")
+                        .build().unwrap()
+                ])
+                .build().unwrap()
+        ])
+        .fields(vec![
+            FieldDefBuilder::default()
+                .name("resume_button")
+                .getter_impl(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::PauseView")
+                    .build().unwrap()))
+                .data_type(DataType::swift_generic(Some("B"),
+                    DataType::swift_struct("Button", None)))
+                .build().unwrap(),
+
+            FieldDefBuilder::default()
+                .name("restart_button")
+                .getter_impl(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::PauseView")
+                    .build().unwrap()))
+                .data_type(DataType::swift_generic(Some("B"),
+                    DataType::swift_struct("Button", None)))
+                .build().unwrap(),
+
+            FieldDefBuilder::default()
+                .name("next_puzzle_button")
+                .getter_impl(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::PauseView")
+                    .build().unwrap()))
+                .data_type(DataType::swift_generic(Some("B"),
+                    DataType::swift_struct("Button", None)))
+                .build().unwrap(),
+
+            FieldDefBuilder::default()
+                .name("main_menu_button")
+                .getter_impl(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::PauseView")
+                    .build().unwrap()))
+                .data_type(DataType::swift_generic(Some("B"),
+                    DataType::swift_struct("Button", None)))
+                .build().unwrap()
+        ])
+        .methods(vec![
+            MethodDefBuilder::default()
+                .name("transition_to_game_view")
+                .impl_block(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::PauseView")
+                    .build().unwrap()))
+                .build().unwrap(),
+
+            MethodDefBuilder::default()
+                .name("transition_to_main_menu_view")
+                .impl_block(Some(ImplBlockDefBuilder::default()
+                    .trait_name("ui::PauseView")
+                    .build().unwrap()))
                 .build().unwrap()
         ])
         .build().unwrap(),
@@ -837,6 +1224,28 @@ lazy_static!{
                     DataType::swift_struct("TextureLoader", None)))
                 .build().unwrap()
         ])
+        .methods(vec![
+            MethodDefBuilder::default()
+                .name("load_progress")
+                .impl_block(Some(ImplBlockDefBuilder::default()
+                    .trait_name("native::SystemView")
+                    .build().unwrap()))
+                .return_type(Some(STRING.clone()))
+                .build().unwrap(),
+
+            MethodDefBuilder::default()
+                .name("save_progress")
+                .impl_block(Some(ImplBlockDefBuilder::default()
+                    .trait_name("native::SystemView")
+                    .build().unwrap()))
+                .arguments(vec![
+                    ArgumentDefBuilder::default()
+                      .name("progress")
+                      .data_type(STRING.clone())
+                      .build().unwrap()
+                ])
+                .build().unwrap()
+        ])
         .build().unwrap(),
 
     TypeDefBuilder::default()
@@ -886,8 +1295,7 @@ handlebars_helper!(lower_camel: |to_convert: str| {
   to_convert.to_mixed_case()
 });
 
-pub fn generate() {
-
+fn create_handlebars() -> Handlebars {
   let mut hb = Handlebars::new();
 
   hb.register_escape_fn(|data| {String::from(data) });
@@ -904,6 +1312,13 @@ pub fn generate() {
       "build/templates/swift_to_rust_binding.handlebars")
           .expect("Failed to load swift template");
 
+  hb
+}
+
+pub fn generate() {
+
+  let hb = create_handlebars();
+
   let mut rust_imports_set : BTreeSet<String> = BTreeSet::new();
 
   let mut renderable_types : Vec<RenderableType>
@@ -979,4 +1394,64 @@ pub fn generate() {
         .expect("Failed to render RustBinder");
 
   }
-}
\ No newline at end of file
+}
+
+/// Get the generated code for the rust owned function with the given name,
+/// up to the next exported function
+#[cfg(test)]
+fn rendered_function<'a>(rendered: &'a str, name: &str) -> &'a str {
+  let start = rendered.find(&format!("pub extern \"C\" fn {}\n", name))
+      .expect(&format!("{} was not rendered", name));
+  let end = rendered[start..].find("#[no_mangle]")
+      .map(|end| start + end)
+      .unwrap_or(rendered.len());
+
+  &rendered[start..end]
+}
+
+#[test]
+fn test_rust_owned_functions_guard_null_self() {
+  let type_def = TypeDefBuilder::default()
+      .name("Guarded")
+      .rust_owned(true)
+      .methods(vec![
+          MethodDefBuilder::default()
+              .name("unwinding")
+              .allow_unwind(true)
+              .return_type(Some(LONG.clone()))
+              .build().unwrap(),
+
+          MethodDefBuilder::default()
+              .name("with_panic_value")
+              .panic_return_value(Some("-1"))
+              .return_type(Some(LONG.clone()))
+              .build().unwrap()
+      ])
+      .build().unwrap();
+
+  let rendered = create_handlebars()
+      .render("rust_to_swift_binding", &RenderableContext {
+        types: vec![RenderableType::from_def(&type_def)],
+        rust_imports: Vec::new(),
+        wrapped_types: Vec::new()
+      })
+      .expect("Failed to render rust bindings");
+
+  let unwinding = rendered_function(&rendered, "guarded__unwinding");
+
+  assert!(unwinding.contains("if _self.is_null() {"));
+  assert!(unwinding.contains("return 0"));
+  assert!(!unwinding.contains("catch_unwind"));
+
+  let with_panic_value
+      = rendered_function(&rendered, "guarded__with_panic_value");
+
+  assert!(with_panic_value.contains("if _self.is_null() {"));
+  assert!(with_panic_value.contains("return -1"));
+  assert!(with_panic_value.contains("catch_unwind"));
+  assert!(!with_panic_value.contains("return 0"));
+
+  // The guard has to come before the pointer is dereferenced
+  assert!(with_panic_value.find("is_null").unwrap()
+      < with_panic_value.find("&*_self").unwrap());
+}
@@ -92,6 +92,10 @@ impl TypeDef {
           .custom_rust_code(method_def.custom_rust_code.map(|cc| {
                 String::from(cc)
               }))
+          .allow_unwind(method_def.allow_unwind)
+          .panic_return_value(method_def.panic_return_value.map(|value| {
+                String::from(value)
+              }))
           .rust_owned(self.rust_owned)
 
           .build().unwrap();
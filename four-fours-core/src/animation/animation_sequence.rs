@@ -0,0 +1,58 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use animation::Tween;
+
+/// Callback receiving the eased progress of an animation step
+pub(crate) type UpdateCallback<C> = Arc<Fn(&C, f64)>;
+
+/// Callback run when an animation finishes
+pub(crate) type FinishCallback<C> = Arc<Fn(&C)>;
+
+/// A sequence of tweens run one after another.  Each step reports its eased
+/// progress to its update callback, and the finish callback is called once
+/// the last step completes.  Callbacks are given the context the animation
+/// is run against so they don't need to hold on to it themselves
+pub struct Animation<C> {
+  pub(crate) steps: Vec<(Tween, UpdateCallback<C>)>,
+  pub(crate) on_finish: Option<FinishCallback<C>>
+}
+
+impl <C> Animation<C> {
+
+  /// Create an animation with a single step
+  pub fn new<F>(tween: Tween, on_update: F) -> Animation<C>
+      where F: Fn(&C, f64) + 'static {
+    let on_update : UpdateCallback<C> = Arc::new(on_update);
+
+    Animation {
+      steps: vec![(tween, on_update)],
+      on_finish: None
+    }
+  }
+
+  /// Add a step that starts when the previous step finishes
+  pub fn then<F>(mut self, tween: Tween, on_update: F) -> Animation<C>
+      where F: Fn(&C, f64) + 'static {
+    let on_update : UpdateCallback<C> = Arc::new(on_update);
+
+    self.steps.push((tween, on_update));
+    self
+  }
+
+  /// Set the callback to call once the whole sequence has finished.  This
+  /// is not called if the animation is cancelled
+  pub fn on_finish<F>(mut self, on_finish: F) -> Animation<C>
+      where F: Fn(&C) + 'static {
+    self.on_finish = Some(Arc::new(on_finish));
+    self
+  }
+
+  /// Time from the start of the animation until its last step finishes
+  pub fn total_duration(&self) -> Duration {
+    self.steps.iter()
+        .fold(Duration::from_millis(0), |total, (tween, _)| {
+          total + tween.total_duration()
+        })
+  }
+}
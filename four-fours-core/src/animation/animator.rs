@@ -0,0 +1,244 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use animation::Animation;
+use animation::animation_sequence::{UpdateCallback, FinishCallback};
+
+pub type AnimationId = usize;
+
+struct RunningAnimation<C> {
+  id: AnimationId,
+  animation: Animation<C>,
+  elapsed: Duration,
+  completed_steps: usize
+}
+
+/// Runs animations against a context.  Time only moves forward when
+/// `advance` is called, so the same sequence of calls always produces the
+/// same sequence of updates
+pub struct Animator<C> {
+  next_id: AtomicUsize,
+  running: Mutex<Vec<RunningAnimation<C>>>
+}
+
+impl <C> Default for Animator<C> {
+  fn default() -> Animator<C> {
+    Animator {
+      next_id: AtomicUsize::new(1),
+      running: Mutex::new(Vec::default())
+    }
+  }
+}
+
+impl <C> Animator<C> {
+
+  /// Start running the given animation from the next call to `advance`
+  pub fn start(&self, animation: Animation<C>) -> AnimationId {
+    let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+
+    self.running.lock().expect("Failed to lock running animations")
+        .push(RunningAnimation {
+          id: id,
+          animation: animation,
+          elapsed: Duration::from_millis(0),
+          completed_steps: 0
+        });
+
+    id
+  }
+
+  /// Stop the animation with the given id without finishing it.  Returns
+  /// whether the animation was still running
+  pub fn cancel(&self, id: AnimationId) -> bool {
+    let mut running = self.running.lock()
+        .expect("Failed to lock running animations");
+    let count_before = running.len();
+
+    running.retain(|r| r.id != id);
+
+    running.len() < count_before
+  }
+
+  /// Stop all running animations without finishing them
+  pub fn cancel_all(&self) {
+    self.running.lock().expect("Failed to lock running animations").clear();
+  }
+
+  /// Jump every running animation to its end, giving each remaining step
+  /// its final update and calling the finish callbacks.  Animations started
+  /// by those callbacks are left running
+  pub fn finish_all(&self, context: &C) {
+    let finished : Vec<RunningAnimation<C>> = self.running.lock()
+        .expect("Failed to lock running animations")
+        .drain(..)
+        .collect();
+
+    for r in finished {
+      for (tween, on_update) in r.animation.steps.iter()
+          .skip(r.completed_steps) {
+        if let Some(progress) = tween.progress_at(tween.total_duration()) {
+          on_update(context, progress);
+        }
+      }
+
+      if let Some(ref on_finish) = r.animation.on_finish {
+        on_finish(context);
+      }
+    }
+  }
+
+  pub fn is_running(&self) -> bool {
+    !self.running.lock().expect("Failed to lock running animations")
+        .is_empty()
+  }
+
+  /// Move all running animations forward by the given time and call their
+  /// callbacks.  Steps that are passed over entirely are still given their
+  /// final update.  Callbacks are called without holding any locks so they
+  /// are free to start or cancel animations.  Returns whether any
+  /// animations are still running
+  pub fn advance(&self, context: &C, delta: Duration) -> bool {
+    let mut updates : Vec<(UpdateCallback<C>, f64)> = Vec::default();
+    let mut finishes : Vec<FinishCallback<C>> = Vec::default();
+
+    {
+      let mut running = self.running.lock()
+          .expect("Failed to lock running animations");
+
+      for r in running.iter_mut() {
+        r.elapsed += delta;
+
+        let mut step_start = r.animation.steps.iter()
+            .take(r.completed_steps)
+            .fold(Duration::from_millis(0), |total, (tween, _)| {
+              total + tween.total_duration()
+            });
+
+        while let Some((tween, on_update)) =
+            r.animation.steps.get(r.completed_steps) {
+
+          if r.elapsed < step_start {
+            break
+          }
+
+          let step_elapsed = r.elapsed - step_start;
+
+          if let Some(progress) = tween.progress_at(step_elapsed) {
+            updates.push((on_update.clone(), progress));
+          }
+
+          if step_elapsed < tween.total_duration() {
+            break
+          }
+
+          step_start += tween.total_duration();
+          r.completed_steps += 1;
+        }
+
+        if r.completed_steps == r.animation.steps.len() {
+          if let Some(ref on_finish) = r.animation.on_finish {
+            finishes.push(on_finish.clone());
+          }
+        }
+      }
+
+      running.retain(|r| r.completed_steps < r.animation.steps.len());
+    }
+
+    for (on_update, progress) in updates {
+      on_update(context, progress);
+    }
+
+    for on_finish in finishes {
+      on_finish(context);
+    }
+
+    self.is_running()
+  }
+}
+
+#[cfg(test)]
+use std::cell::RefCell;
+
+#[cfg(test)]
+use animation::{Easing, Tween};
+
+#[test]
+fn test_advance() {
+  let log : RefCell<Vec<String>> = RefCell::new(Vec::default());
+  let animator : Animator<RefCell<Vec<String>>> = Animator::default();
+  let step = Tween::new(Duration::from_millis(100), Easing::Linear);
+
+  animator.start(
+      Animation::new(step.clone(), |log: &RefCell<Vec<String>>, p| {
+        log.borrow_mut().push(format!("a{}", p))
+      })
+      .then(step.delayed(Duration::from_millis(100)), |log, p| {
+        log.borrow_mut().push(format!("b{}", p))
+      })
+      .on_finish(|log| log.borrow_mut().push(String::from("done"))));
+
+  assert!(animator.advance(&log, Duration::from_millis(50)));
+  assert!(animator.advance(&log, Duration::from_millis(100)));
+  assert!(animator.advance(&log, Duration::from_millis(100)));
+  assert!(!animator.advance(&log, Duration::from_millis(100)));
+
+  assert_eq!(*log.borrow(), vec!["a0.5", "a1", "b0.5", "b1", "done"]);
+}
+
+#[test]
+fn test_skipped_steps_finish() {
+  let log : RefCell<Vec<String>> = RefCell::new(Vec::default());
+  let animator : Animator<RefCell<Vec<String>>> = Animator::default();
+  let step = Tween::new(Duration::from_millis(100), Easing::EaseInOut);
+
+  animator.start(
+      Animation::new(step.clone(), |log: &RefCell<Vec<String>>, p| {
+        log.borrow_mut().push(format!("a{}", p))
+      })
+      .then(step, |log, p| log.borrow_mut().push(format!("b{}", p))));
+
+  assert!(!animator.advance(&log, Duration::from_secs(1)));
+  assert_eq!(*log.borrow(), vec!["a1", "b1"]);
+}
+
+#[test]
+fn test_cancel() {
+  let log : RefCell<Vec<String>> = RefCell::new(Vec::default());
+  let animator : Animator<RefCell<Vec<String>>> = Animator::default();
+  let step = Tween::new(Duration::from_millis(100), Easing::Linear);
+
+  let id = animator.start(
+      Animation::new(step, |log: &RefCell<Vec<String>>, p| {
+        log.borrow_mut().push(format!("a{}", p))
+      })
+      .on_finish(|log| log.borrow_mut().push(String::from("done"))));
+
+  animator.advance(&log, Duration::from_millis(50));
+
+  assert!(animator.cancel(id));
+  assert!(!animator.cancel(id));
+  assert!(!animator.advance(&log, Duration::from_millis(100)));
+  assert_eq!(*log.borrow(), vec!["a0.5"]);
+}
+
+#[test]
+fn test_finish_all() {
+  let log : RefCell<Vec<String>> = RefCell::new(Vec::default());
+  let animator : Animator<RefCell<Vec<String>>> = Animator::default();
+  let step = Tween::new(Duration::from_millis(100), Easing::Linear);
+
+  animator.start(
+      Animation::new(step.clone(), |log: &RefCell<Vec<String>>, p| {
+        log.borrow_mut().push(format!("a{}", p))
+      })
+      .then(step, |log, p| log.borrow_mut().push(format!("b{}", p)))
+      .on_finish(|log| log.borrow_mut().push(String::from("done"))));
+
+  animator.advance(&log, Duration::from_millis(150));
+  animator.finish_all(&log);
+
+  assert!(!animator.is_running());
+  assert_eq!(*log.borrow(), vec!["a1", "b0.5", "b1", "done"]);
+}
@@ -0,0 +1,72 @@
+
+/// Overshoot used by `Easing::BackOut`
+const BACK_OVERSHOOT : f64 = 1.70158;
+
+/// Curves mapping linear progress through an animation to eased progress.
+/// All curves start at 0 and end at 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+  Linear,
+  EaseIn,
+  EaseOut,
+  EaseInOut,
+
+  /// Overshoots the end slightly before settling
+  BackOut
+}
+
+impl Easing {
+
+  /// Get the eased progress for the given linear progress.  The input is
+  /// clamped to the range 0 to 1
+  pub fn apply(&self, t: f64) -> f64 {
+    let t = t.max(0.).min(1.);
+
+    match *self {
+      Easing::Linear => t,
+      Easing::EaseIn => t * t * t,
+      Easing::EaseOut => 1. - (1. - t).powi(3),
+      Easing::EaseInOut => {
+        if t < 0.5 {
+          4. * t * t * t
+        }
+        else {
+          1. - (-2. * t + 2.).powi(3) / 2.
+        }
+      },
+      Easing::BackOut => {
+        let u = t - 1.;
+        1. + (BACK_OVERSHOOT + 1.) * u.powi(3) + BACK_OVERSHOOT * u.powi(2)
+      }
+    }
+  }
+}
+
+impl Default for Easing {
+  fn default() -> Easing {
+    Easing::EaseInOut
+  }
+}
+
+#[test]
+fn test_apply() {
+  let curves = [
+    Easing::Linear,
+    Easing::EaseIn,
+    Easing::EaseOut,
+    Easing::EaseInOut,
+    Easing::BackOut
+  ];
+
+  for curve in curves.iter() {
+    assert!(curve.apply(0.).abs() < 1e-9);
+    assert!((curve.apply(1.) - 1.).abs() < 1e-9);
+    assert_eq!(curve.apply(2.), curve.apply(1.));
+  }
+
+  assert_eq!(Easing::Linear.apply(0.25), 0.25);
+  assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+  assert!(Easing::EaseIn.apply(0.5) < 0.5);
+  assert!(Easing::EaseOut.apply(0.5) > 0.5);
+  assert!(Easing::BackOut.apply(0.8) > 1.);
+}
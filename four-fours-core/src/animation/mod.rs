@@ -0,0 +1,10 @@
+pub use self::easing::Easing;
+pub use self::tween::Tween;
+pub(crate) use self::tween::to_secs;
+pub use self::animation_sequence::Animation;
+pub use self::animator::{Animator, AnimationId};
+
+mod easing;
+mod tween;
+mod animation_sequence;
+mod animator;
@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use animation::Easing;
+
+/// Timing of a single animation step: how long it waits before starting,
+/// how long it runs and how its progress is eased
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct Tween {
+  #[get = "pub"] delay: Duration,
+  #[get = "pub"] duration: Duration,
+  #[get = "pub"] easing: Easing
+}
+
+impl Tween {
+
+  pub fn new(duration: Duration, easing: Easing) -> Tween {
+    Tween {
+      delay: Duration::from_millis(0),
+      duration: duration,
+      easing: easing
+    }
+  }
+
+  /// Get a copy of this tween that waits for the given time before starting
+  pub fn delayed(&self, delay: Duration) -> Tween {
+    Tween {
+      delay: delay,
+      .. self.clone()
+    }
+  }
+
+  /// Time from the start of this tween's delay until it is finished
+  pub fn total_duration(&self) -> Duration {
+    self.delay + self.duration
+  }
+
+  /// Get the eased progress of this tween the given time after it was
+  /// started, or none if it is still waiting out its delay
+  pub fn progress_at(&self, elapsed: Duration) -> Option<f64> {
+    if elapsed < self.delay {
+      return None
+    }
+
+    let running = to_secs(elapsed - self.delay);
+    let duration = to_secs(self.duration);
+
+    let t = if duration > 0. { running / duration } else { 1. };
+
+    Some(self.easing.apply(t))
+  }
+}
+
+pub(crate) fn to_secs(duration: Duration) -> f64 {
+  duration.as_secs() as f64 + f64::from(duration.subsec_nanos()) * 1e-9
+}
+
+#[test]
+fn test_progress_at() {
+  let tween = Tween::new(Duration::from_millis(100), Easing::Linear)
+      .delayed(Duration::from_millis(50));
+
+  assert_eq!(tween.total_duration(), Duration::from_millis(150));
+  assert_eq!(tween.progress_at(Duration::from_millis(20)), None);
+  assert_eq!(tween.progress_at(Duration::from_millis(50)), Some(0.));
+  assert_eq!(tween.progress_at(Duration::from_millis(100)), Some(0.5));
+  assert_eq!(tween.progress_at(Duration::from_millis(500)), Some(1.));
+
+  let instant = Tween::new(Duration::from_millis(0), Easing::Linear);
+
+  assert_eq!(instant.progress_at(Duration::from_millis(0)), Some(1.));
+}
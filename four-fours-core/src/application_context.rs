@@ -1,49 +1,80 @@
 
 use std::ops::Deref;
 use std::sync::{Arc, RwLock};
-use event::EventBus;
-use log::SetLoggerError;
-use simplelog::{ SimpleLogger, LevelFilter, Config, CombinedLogger };
+use std::thread;
+use event::{
+    EnterBackground,
+    EnterForeground,
+    EventBus,
+    LowMemory,
+    Terminate
+};
+use logging::{self, LogConfig, LogSink};
 
 use ::{
     WrappedLoadingPresenter,
     WrappedMainMenuPresenter,
     WrappedGamePresenter,
+    WrappedPausePresenter,
     LoadingView,
     MainMenuView,
     GameView,
+    PauseView,
     SystemView
 };
 
+use native;
 use native::{RuntimeResources};
 
+use replay::{InputRecorder, Recording, ReplayPlayer};
+
+use model::{
+    GameSetup,
+    GameState,
+    NavigationStack,
+    PlayerProgress,
+    Screen
+};
+
 use presenter::{
+    GamePresenterQueue,
     LoadingPresenter,
     MainMenuPresenter,
-    GamePresenter
+    GamePresenter,
+    PausePresenter
 };
 
-lazy_static!{
-  static ref LOGGER_RESULT : Result<(), SetLoggerError>
-      = CombinedLogger::init(
-          vec![
-              SimpleLogger::new(LevelFilter::Debug, Config::default())
-          ]
-      );
-}
-
 pub struct ApplicationContext(Arc<ApplicationContextInner>);
 
 impl ApplicationContext {
 
-  pub fn new(system_view: SystemView) -> ApplicationContext {
-    if LOGGER_RESULT.is_err() {
-      println!("Failed to set logger")
+  /// Create the application, sending log records allowed by the given
+  /// configuration to the given sink.  Records are logged from every
+  /// thread, so the sink must be safe to call from any of them
+  pub fn new(system_view: SystemView,
+      log_config: LogConfig,
+      log_sink: LogSink) -> ApplicationContext {
+    let system_view = Arc::new(system_view);
+
+    let logging_result = logging::init_logging(log_config, log_sink);
+
+    if let Err(e) = logging_result {
+      // Whatever logger is already set still gets this
+      warn!("{}", e);
     }
+
+    let progress = load_progress(&system_view);
+
+    let event_bus = EventBus::new();
+
     ApplicationContext(Arc::new(ApplicationContextInner {
-      event_bus: EventBus::new(),
-      system_view: Arc::new(system_view),
-      runtime_resources: RwLock::new(None)
+      event_bus: event_bus.clone(),
+      system_view: system_view,
+      game_presenters: GamePresenterQueue::new(event_bus.clone()),
+      game_state: RwLock::new(None),
+      progress: Arc::new(RwLock::new(progress)),
+      navigation: Arc::new(RwLock::new(NavigationStack::default())),
+      recorder: Arc::new(InputRecorder::default())
     }))
   }
 }
@@ -59,13 +90,19 @@ impl Deref for ApplicationContext {
 pub struct ApplicationContextInner {
   event_bus: Arc<EventBus>,
   system_view: Arc<SystemView>,
-  runtime_resources: RwLock<Option<Arc<RuntimeResources<SystemView>>>>
+  game_presenters: GamePresenterQueue<GameView,SystemView>,
+  game_state: RwLock<Option<GameState>>,
+  progress: Arc<RwLock<PlayerProgress>>,
+  navigation: Arc<RwLock<NavigationStack>>,
+  recorder: Arc<InputRecorder>
 }
 
 impl ApplicationContext {
 
   pub fn bind_to_loading_view(&self, view: LoadingView)
       -> WrappedLoadingPresenter {
+    self.show_screen(Screen::Loading);
+
     let self_copy = self.0.clone();
 
     WrappedLoadingPresenter::new(
@@ -74,51 +111,321 @@ impl ApplicationContext {
             self.system_view.clone(),
             self.event_bus.clone(),
             Box::new( move | resources | {
-              self_copy.set_runtime_resources(resources);
+              ApplicationContextInner::set_runtime_resources(
+                  &self_copy, resources);
             })))
   }
 
   pub fn bind_to_main_menu_view(&self, view: MainMenuView)
       -> WrappedMainMenuPresenter {
+    self.show_screen(Screen::MainMenu);
+
+    let self_copy = self.0.clone();
+
     WrappedMainMenuPresenter::new(
-        MainMenuPresenter::new(view, self.event_bus.clone()))
+        MainMenuPresenter::new(
+            view,
+            self.event_bus.clone(),
+            self.progress.clone(),
+            Box::new( move | game_state | {
+              self_copy.set_game_state(game_state);
+            }),
+            self.recorder.clone()))
   }
 
+  /// Bind the game view to a new game presenter.  If the runtime resources
+  /// haven't finished loading, the presenter is created once they have
   pub fn bind_to_game_view(&self, view: GameView)
       -> WrappedGamePresenter {
-    WrappedGamePresenter::new(
-        GamePresenter::new(
+    self.show_screen(Screen::Game);
+
+    let game_state = self.take_game_state();
+
+    let self_copy = self.0.clone();
+
+    WrappedGamePresenter::new(self.game_presenters.bind(
+        view,
+        game_state,
+        move |view, runtime_resources, game_state| {
+          ApplicationContextInner::create_game_presenter(
+              &self_copy, view, runtime_resources, game_state)
+        }))
+  }
+
+  pub fn bind_to_pause_view(&self, view: PauseView)
+      -> WrappedPausePresenter {
+    self.show_screen(Screen::Pause);
+
+    let self_copy = self.0.clone();
+
+    WrappedPausePresenter::new(
+        PausePresenter::new(
             view,
             self.event_bus.clone(),
-            self.get_runtime_resources()))
+            self.peek_game_state(),
+            self.progress.clone(),
+            self.navigation.clone(),
+            Box::new( move | game_state_opt | {
+              match game_state_opt {
+                Some(game_state) => self_copy.set_game_state(game_state),
+                None => self_copy.clear_game_state()
+              }
+            }),
+            self.recorder.clone()))
+  }
+
+  /// Called by the native side when the app moves to the background.
+  /// Progress is saved right away in case the app isn't brought back, and
+  /// presenters are told to save their state.  This is called on the main
+  /// thread, which listeners may need to update their views, so it doesn't
+  /// wait for them
+  pub fn enter_background(&self) {
+    info!("Entering background");
+
+    self.save_progress();
+    self.event_bus.post(EnterBackground{});
+  }
+
+  /// Called by the native side when the app returns to the foreground so
+  /// presenters can restore what they saved on entering the background
+  pub fn enter_foreground(&self) {
+    info!("Entering foreground");
+
+    self.event_bus.post(EnterForeground{});
+  }
+
+  /// Called by the native side when the system is running low on memory.
+  /// Textures are unloaded and loaded again the next time they're needed
+  pub fn low_memory(&self) {
+    warn!("Received a low memory warning");
+
+    if let Ok(runtime_resources) = self.get_runtime_resources() {
+      if runtime_resources.textures().unload() {
+        info!("Unloaded textures");
+      }
+    }
+
+    self.event_bus.post(LowMemory{});
+  }
+
+  /// Called by the native side when the app is about to exit.  Progress is
+  /// saved before returning since listeners may not get to run, and like
+  /// `enter_background` this doesn't wait for them
+  pub fn terminate(&self) {
+    info!("Terminating");
+
+    self.save_progress();
+    self.event_bus.post(Terminate{});
+  }
+
+  /// Start recording the player's input and the results it produces,
+  /// discarding anything recorded so far
+  pub fn start_recording(&self) {
+    self.recorder.start_recording();
+  }
+
+  /// Stop recording and write what was recorded to the file at the given
+  /// path.  Returns false if nothing was being recorded or the file couldn't
+  /// be written
+  pub fn save_recording(&self, path: String) -> bool {
+    match self.recorder.stop_recording() {
+      Some(recording) => match recording.save(&path) {
+        Ok(_) => true,
+        Err(e) => {
+          error!("Failed to save recording to {}: {}", path, e);
+          false
+        }
+      },
+      None => {
+        warn!("Asked to save a recording but none was started");
+        false
+      }
+    }
+  }
+
+  /// Start recording every event posted in the core, replacing any trace in
+  /// progress
+  pub fn start_event_trace(&self) {
+    self.event_bus.start_tracing();
+  }
+
+  /// Stop tracing events and write the trace to the file at the given path,
+  /// as JSON lines if the path ends in `.jsonl` or in the Chrome trace
+  /// format otherwise.  Returns false if no trace was started or the file
+  /// couldn't be written
+  pub fn save_event_trace(&self, path: String) -> bool {
+    match self.event_bus.stop_tracing() {
+      Some(tracer) => match tracer.save(&path) {
+        Ok(_) => true,
+        Err(e) => {
+          error!("Failed to save event trace to {}: {}", path, e);
+          false
+        }
+      },
+      None => {
+        warn!("Asked to save an event trace but none was started");
+        false
+      }
+    }
+  }
+
+  /// Play back the recording in the file at the given path against the
+  /// views bound to this context.  The inputs are sent from a thread of
+  /// their own, since playing them back with their recorded timing takes
+  /// as long as the recording did, so this returns as soon as playback has
+  /// started.  Returns false if the recording couldn't be loaded
+  pub fn replay_recording(&self, path: String) -> bool {
+    let recording = match Recording::load(&path) {
+      Ok(recording) => recording,
+      Err(e) => {
+        error!("Failed to load recording from {}: {}", path, e);
+        return false
+      }
+    };
+
+    let recorder = self.recorder.clone();
+
+    let spawned = thread::Builder::new()
+        .name(String::from("replay"))
+        .spawn(move || {
+          match ReplayPlayer::new(recorder).with_speed(1.).play(&recording) {
+            Ok(sent) => info!("Replayed {} inputs from {}", sent, path),
+            Err(e) => error!("Failed to replay {}: {}", path, e)
+          }
+        });
+
+    match spawned {
+      Ok(_) => true,
+      Err(e) => {
+        error!("Failed to start replay: {}", e);
+        false
+      }
+    }
   }
 }
 
 impl ApplicationContextInner {
 
-  pub fn set_runtime_resources(&self,
+  /// Store the loaded runtime resources and create the presenter for any
+  /// game view that was bound while they were loading
+  pub fn set_runtime_resources(this: &Arc<ApplicationContextInner>,
       runtime_resources: RuntimeResources<SystemView>) {
-    if let Ok(mut runtime_resources_guard) = self.runtime_resources.write() {
-      *runtime_resources_guard = Some(Arc::new(runtime_resources));
+    let unbound_game_state = this.game_presenters.set_runtime_resources(
+        runtime_resources,
+        |view, runtime_resources, game_state| {
+          ApplicationContextInner::create_game_presenter(
+              this, view, runtime_resources, game_state)
+        });
+
+    if let Some(game_state) = unbound_game_state {
+      this.set_game_state(game_state);
+    }
+  }
+
+  fn create_game_presenter(this: &Arc<ApplicationContextInner>,
+      view: GameView,
+      runtime_resources: Arc<RuntimeResources<SystemView>>,
+      game_state: GameState) -> Arc<GamePresenter<GameView,SystemView>> {
+    let self_copy = this.clone();
+    let other_self_copy = this.clone();
+
+    GamePresenter::new(
+        view,
+        this.event_bus.clone(),
+        runtime_resources,
+        game_state,
+        this.progress.clone(),
+        Box::new( move || {
+          self_copy.save_progress();
+        }),
+        Box::new( move | game_state | {
+          other_self_copy.set_game_state(game_state);
+        }),
+        this.recorder.clone())
+  }
+
+  pub fn set_game_state(&self, game_state: GameState) {
+    if let Ok(mut game_state_guard) = self.game_state.write() {
+      *game_state_guard = Some(game_state);
     }
     else {
-      error!("Failed to unlock runtime_resources for writing");
+      error!("Failed to unlock game_state for writing");
     }
   }
 
-  pub fn get_runtime_resources(&self) -> Arc<RuntimeResources<SystemView>> {
-    if let Ok(runtime_resources_guard) = self.runtime_resources.read() {
-      if let Some(runtime_resources) = runtime_resources_guard.as_ref() {
-        runtime_resources.clone()
+  pub fn clear_game_state(&self) {
+    if let Ok(mut game_state_guard) = self.game_state.write() {
+      *game_state_guard = None;
+    }
+    else {
+      error!("Failed to unlock game_state for writing");
+    }
+  }
+
+  /// Get a copy of the game state chosen for the next game without taking
+  /// it, if one has been chosen
+  pub fn peek_game_state(&self) -> Option<GameState> {
+    match self.game_state.read() {
+      Ok(game_state_guard) => game_state_guard.clone(),
+      Err(_) => {
+        error!("Failed to unlock game_state for reading");
+        None
       }
-      else {
-        error!("Runtime resources has not been set");
-        panic!("Runtime resources has not been set");
+    }
+  }
+
+  /// Record that the given screen is now showing in the navigation stack
+  pub fn show_screen(&self, screen: Screen) {
+    if let Ok(mut navigation_guard) = self.navigation.write() {
+      navigation_guard.show(screen);
+    }
+    else {
+      error!("Failed to unlock navigation for writing");
+    }
+  }
+
+  /// Take the game state chosen for the next game, or the classic four fours
+  /// setup if none has been chosen
+  pub fn take_game_state(&self) -> GameState {
+    let chosen_state = match self.game_state.write() {
+      Ok(mut game_state_guard) => game_state_guard.take(),
+      Err(_) => {
+        error!("Failed to unlock game_state for writing");
+        None
       }
+    };
+
+    chosen_state.unwrap_or_else(|| GameState::new(GameSetup::classic()))
+  }
+
+  /// Write the player's current progress to the native platform's storage
+  pub fn save_progress(&self) {
+    if let Ok(progress_guard) = self.progress.read() {
+      native::SystemView::save_progress(
+          &*self.system_view,
+          progress_guard.to_save_string());
     }
     else {
-      error!("Failed to unlock runtime_resources for reading");
-      panic!("Failed to unlock runtime_resources for reading");
+      error!("Failed to unlock progress for reading");
+    }
+  }
+
+  /// Get the runtime resources, or an error if they haven't finished
+  /// loading
+  pub fn get_runtime_resources(&self)
+      -> Result<Arc<RuntimeResources<SystemView>>, String> {
+    self.game_presenters.get_runtime_resources()
+  }
+}
+
+fn load_progress(system_view: &SystemView) -> PlayerProgress {
+  let saved = native::SystemView::load_progress(system_view);
+
+  match PlayerProgress::from_save_string(&saved) {
+    Ok(progress) => progress,
+    Err(e) => {
+      warn!("Discarding unreadable player progress: {}", e);
+      PlayerProgress::default()
     }
   }
 }
\ No newline at end of file
@@ -0,0 +1,29 @@
+/// How an event bus runs the listeners for posted events
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DispatchMode {
+  /// Events are handled by a pool of the given number of worker threads, so
+  /// events posted one after another may be handled in any order.  Delayed
+  /// events are posted by a timer thread in real time
+  ThreadPool(usize),
+
+  /// Events are queued in the order they're posted and handled one at a
+  /// time when `drain` is called.  The listeners still run on the event
+  /// emitter's threads while the draining thread waits for each event.
+  /// Delayed events are posted when `advance_clock` is called
+  Ordered,
+
+  /// Events are handled before `post` returns, so nothing the listeners
+  /// lock may be held while posting.  Events posted by listeners are
+  /// queued and handled after the event that posted them, before the
+  /// outermost `post` returns.  If another thread is already handling
+  /// events, `post` leaves the event for that thread and returns without
+  /// waiting for it.  Delayed events are posted when `advance_clock` is
+  /// called
+  Inline
+}
+
+impl Default for DispatchMode {
+  fn default() -> DispatchMode {
+    DispatchMode::ThreadPool(8)
+  }
+}
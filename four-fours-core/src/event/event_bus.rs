@@ -6,7 +6,17 @@ extern crate futures;
 extern crate trace_error;
 
 use event::FourFoursEvent;
-use event::{EventListener, ListenerRegistration};
+use event::{
+  DispatchMode,
+  EventBusMetrics,
+  EventListener,
+  EventTracer,
+  ListenerRegistration,
+  TypedEvent
+};
+use util::panic_message;
+
+use event::event_timer::{EventTimer, ScheduledPost};
 
 use self::rayon::ThreadPoolBuilder;
 use self::crossbeam_channel::{Sender, Receiver};
@@ -14,69 +24,192 @@ use self::futures::future::Future;
 use self::trace_error::Trace;
 use self::parallel_event_emitter::{ParallelEventEmitter, EventError};
 
-use std::sync::{Arc, Mutex};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
 
 type ConsumableFuture = Box<Future<Item = usize, Error = Trace<EventError>> + Send>;
 
+/// Emits a posted event and waits for its listeners when it's called, so
+/// queued events aren't emitted until they're handled
+type Dispatch = Box<Fn() + Send>;
+
+type SharedTracer = Arc<RwLock<Option<Arc<EventTracer>>>>;
+
+/// A payload as it's emitted, numbered so a tracer can tell which post each
+/// listener run belongs to
+#[derive(Debug, Clone)]
+struct Posted<E> {
+  seq: usize,
+  payload: E
+}
+
+/// Counts the events that have been posted but not yet handled
+#[derive(Default)]
+struct Pending {
+  count: Mutex<usize>,
+  idle: Condvar
+}
+
+impl Pending {
+
+  fn add(&self) {
+    *self.count.lock().unwrap() += 1;
+  }
+
+  fn done(&self) {
+    let mut count = self.count.lock().unwrap();
+    *count -= 1;
+
+    if *count == 0 {
+      self.idle.notify_all();
+    }
+  }
+
+  fn wait_until_idle(&self) {
+    let mut count = self.count.lock().unwrap();
+
+    while *count > 0 {
+      count = self.idle.wait(count).unwrap();
+    }
+  }
+}
+
+fn run_dispatch(dispatch: Dispatch, pending: &Pending) {
+  if let Err(cause) = panic::catch_unwind(AssertUnwindSafe(|| dispatch())) {
+    error!("Event dispatch panicked: {}", panic_message(&*cause));
+  }
+
+  pending.done();
+}
+
+fn current_tracer(tracer: &SharedTracer) -> Option<Arc<EventTracer>> {
+  tracer.read().ok().and_then(|tracer| tracer.clone())
+}
+
 pub struct EventBus {
-  sink: Sender<ConsumableFuture>,
+  mode: DispatchMode,
+  sink: Sender<Dispatch>,
+  source: Receiver<Dispatch>,
+  pending: Arc<Pending>,
+  /// Held by the thread draining the queue so queued events are handled
+  /// one at a time
+  draining: Mutex<()>,
+  shut_down: AtomicBool,
+  next_listener_id: AtomicUsize,
+  next_seq: AtomicUsize,
+  metrics: Arc<Mutex<EventBusMetrics>>,
+  tracer: SharedTracer,
+  timer: Arc<EventTimer>,
+  /// Time on the bus's clock, which only moves when it's advanced, unless
+  /// the bus has a thread pool
+  clock: Mutex<Instant>,
   emitter : Arc<Mutex<ParallelEventEmitter<FourFoursEvent>>>
 }
 
 impl EventBus {
 
+  /// Create an event bus that handles events on a pool of worker threads
   pub fn new() -> Arc<EventBus> {
+    EventBus::with_mode(DispatchMode::default())
+  }
+
+  pub fn with_mode(mode: DispatchMode) -> Arc<EventBus> {
 
-    let (sink, source) : (Sender<ConsumableFuture>, Receiver<ConsumableFuture>)
+    let (sink, source) : (Sender<Dispatch>, Receiver<Dispatch>)
         = crossbeam_channel::unbounded();
 
-    let worker_count = 8;
-    let pool = ThreadPoolBuilder::new()
-        .num_threads(worker_count)
-        .build()
-        .unwrap();
-
-    for _ in 0..worker_count {
-      let copied_source = source.clone();
-
-      pool.spawn(move || {
-        loop {
-          match copied_source.recv() {
-            Ok(future) => {
-              let _ = future.wait();
-            },
-            Err(e) => warn!("Receive from channel failed: {:?}", e)
-          };
-        }
-      })
+    let pending = Arc::new(Pending::default());
+
+    if let DispatchMode::ThreadPool(worker_count) = mode {
+      let pool = ThreadPoolBuilder::new()
+          .num_threads(worker_count)
+          .build()
+          .unwrap();
+
+      for _ in 0..worker_count {
+        let copied_source = source.clone();
+        let copied_pending = pending.clone();
+
+        // Receiving fails once the bus has been dropped and the queue is
+        // empty, which stops the worker
+        pool.spawn(move || {
+          while let Ok(dispatch) = copied_source.recv() {
+            run_dispatch(dispatch, &copied_pending);
+          }
+
+          debug!("Event bus worker stopped");
+        })
+      }
     }
 
-    Arc::new(EventBus {
+    let bus = Arc::new(EventBus {
+      mode: mode,
       sink: sink,
+      source: source,
+      pending: pending,
+      draining: Mutex::new(()),
+      shut_down: AtomicBool::new(false),
+      next_listener_id: AtomicUsize::new(0),
+      next_seq: AtomicUsize::new(0),
+      metrics: Arc::new(Mutex::new(EventBusMetrics::default())),
+      tracer: Arc::new(RwLock::new(None)),
+      timer: Arc::new(EventTimer::default()),
+      clock: Mutex::new(Instant::now()),
       emitter : Arc::new(Mutex::new(ParallelEventEmitter::default()))
-    })
-  }
-
-  pub fn register<E,H>(&self,
-      event_type: FourFoursEvent,
-      handler: &Arc<H>) -> ListenerRegistration
-          where
-              E : Send + Clone + Into<FourFoursEvent> + 'static,
-              H : EventListener<E> {
-    self.register_disambiguous(event_type, handler, None)
-  }
-
-  pub fn register_disambiguous<E, H>(
-      &self,
-      event_type: FourFoursEvent,
-      handler: &Arc<H>,
-      _: Option<E>) -> ListenerRegistration
-          where
-              E : Send + Clone + Into<FourFoursEvent> + 'static,
-              H : EventListener<E> {
+    });
+
+    if let DispatchMode::ThreadPool(_) = mode {
+      EventTimer::spawn(&bus.timer, Arc::downgrade(&bus));
+    }
+
+    bus
+  }
+
+  pub fn mode(&self) -> DispatchMode {
+    self.mode
+  }
+
+  /// Get a snapshot of the bus's metrics
+  pub fn metrics(&self) -> EventBusMetrics {
+    let mut metrics = self.metrics.lock().unwrap().clone();
+    metrics.set_queue_depth(self.source.len());
+    metrics
+  }
+
+  /// Start recording every event posted from now on, replacing any trace in
+  /// progress, and get the tracer recording them
+  pub fn start_tracing(&self) -> Arc<EventTracer> {
+    let tracer = Arc::new(EventTracer::new());
+
+    *self.tracer.write().unwrap() = Some(tracer.clone());
+    tracer
+  }
+
+  /// Stop tracing and get the tracer that was recording, if there was one
+  pub fn stop_tracing(&self) -> Option<Arc<EventTracer>> {
+    self.tracer.write().unwrap().take()
+  }
+
+  /// Register the given handler for events with payloads of type `E`.  If
+  /// the handler listens for more than one type of event, the payload type
+  /// has to be given explicitly, e.g. `register::<Layout, _>(&handler)`
+  pub fn register<E,H>(&self, handler: &Arc<H>) -> ListenerRegistration
+      where
+          E : TypedEvent,
+          H : EventListener<E> {
+    let event_type = E::event_type();
     let weak_handler = Arc::downgrade(handler);
 
+    // Identifies the listener in reports, unlike the emitter's ids which are
+    // reused once listeners are removed
+    let reported_id = self.next_listener_id.fetch_add(1, Ordering::SeqCst);
+    let reported_event_type = event_type.clone();
+    let copied_metrics = self.metrics.clone();
+    let copied_tracer = self.tracer.clone();
+
     let mut listener_id = 0u64;
     let borrowed_event_type = event_type.clone();
     let copied_emitter = Arc::downgrade(&self.emitter);
@@ -85,10 +218,26 @@ impl EventBus {
 
     if let Ok(real_listener_id) = locked_emitter.add_listener_value(
         event_type,
-        move |arg_opt: Option<E>| {
-          if let Some(handler) = weak_handler.upgrade() {
-            if let Some(arg) = arg_opt {
-              handler.on_event(&arg);
+        move |arg_opt: Option<Posted<E>>| {
+          if let (Some(handler), Some(arg))
+              = (weak_handler.upgrade(), arg_opt) {
+            let start = Instant::now();
+            let result = panic::catch_unwind(
+                AssertUnwindSafe(|| handler.on_event(&arg.payload)));
+
+            copied_metrics.lock().unwrap()
+                .record_listener(start.elapsed(), result.is_err());
+
+            if let Some(tracer) = current_tracer(&copied_tracer) {
+              tracer.record_listener(
+                  arg.seq, reported_id, start, result.is_err());
+            }
+
+            if let Err(cause) = result {
+              error!("Listener {} for {:?} panicked: {}",
+                  reported_id,
+                  reported_event_type,
+                  panic_message(&*cause));
             }
           }
           Ok(())
@@ -97,7 +246,9 @@ impl EventBus {
     }
 
     ListenerRegistration::new(Box::new( move || {
-      println!("Deregistering {}", listener_id);
+      debug!("Deregistering listener {} for {:?}",
+          reported_id,
+          borrowed_event_type);
       let copied_event_type = borrowed_event_type.clone();
       if listener_id > 0 {
         if let Some(emitter) = copied_emitter.upgrade() {
@@ -110,13 +261,371 @@ impl EventBus {
     } ))
   }
 
+  /// Post the given event to its listeners.  On an inline bus the event is
+  /// handled before this returns, so the caller must not hold any lock the
+  /// listeners take
   pub fn post<E>(&self, e: E)
-      where E : Send + Clone + Into<FourFoursEvent> + 'static {
+      where E : TypedEvent {
 
-    let mut locked_emitter = self.emitter.lock().unwrap();
-    let event_type = e.clone().into();
-    let f = locked_emitter.emit_value(event_type, e.clone());
+    if self.shut_down.load(Ordering::SeqCst) {
+      warn!("Ignoring {:?} posted after shutdown", E::event_type());
+      return
+    }
+
+    let copied_emitter = self.emitter.clone();
+    let copied_metrics = self.metrics.clone();
+    let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+    let tracer = current_tracer(&self.tracer);
+
+    if let Some(ref tracer) = tracer {
+      tracer.record_post(seq, E::event_type(), format!("{:?}", e));
+    }
+
+    let posted = Posted { seq: seq, payload: e };
+
+    self.pending.add();
+    self.sink.send(Box::new(move || {
+      let start = Instant::now();
+      let f : ConsumableFuture = {
+        let mut locked_emitter = copied_emitter.lock().unwrap();
+        Box::new(locked_emitter.emit_value(E::event_type(), posted.clone()))
+      };
+
+      let result = f.wait();
+
+      if let Err(ref error) = result {
+        error!("Failed to handle {:?}: {:?}", E::event_type(), error);
+      }
+
+      copied_metrics.lock().unwrap().record_event(result.is_err());
+
+      if let Some(ref tracer) = tracer {
+        tracer.record_handled(seq, start);
+      }
+    }));
 
-    self.sink.send(Box::new(f));
+    self.metrics.lock().unwrap().set_queue_depth(self.source.len());
+
+    if self.mode == DispatchMode::Inline {
+      self.drain();
+    }
+  }
+
+  /// Post the given event once the given delay has passed on the bus's
+  /// clock.  Listeners aren't blocked while waiting, so this is how a
+  /// listener should put off work rather than sleeping
+  pub fn post_after<E>(&self, delay: Duration, e: E)
+      where E : TypedEvent {
+
+    if self.shut_down.load(Ordering::SeqCst) {
+      warn!("Ignoring {:?} posted after shutdown", E::event_type());
+      return
+    }
+
+    let post : ScheduledPost = Box::new(move |bus| bus.post(e.clone()));
+
+    self.timer.schedule(self.now() + delay, post);
+  }
+
+  /// Get the time on the bus's clock.  This is the real time if the bus has
+  /// a thread pool, otherwise it only moves when `advance_clock` is called
+  pub fn now(&self) -> Instant {
+    match self.mode {
+      DispatchMode::ThreadPool(_) => Instant::now(),
+      DispatchMode::Ordered | DispatchMode::Inline =>
+          *self.clock.lock().expect("Failed to lock event bus clock")
+    }
   }
-}
\ No newline at end of file
+
+  /// Move the clock of a bus without a thread pool forward by the given
+  /// time, posting delayed events as they come due and handling them on the
+  /// calling thread like `drain`.  Events delayed by the listeners of those
+  /// events are posted too if they come due in time.  Must not be called
+  /// from a listener
+  pub fn advance_clock(&self, by: Duration) {
+    if let DispatchMode::ThreadPool(_) = self.mode {
+      warn!("Ignoring clock advance on a bus that runs in real time");
+      return
+    }
+
+    let until = self.now() + by;
+
+    self.drain();
+
+    while let Some((due, post)) = self.timer.take_due(until) {
+      *self.clock.lock().expect("Failed to lock event bus clock") = due;
+
+      post(self);
+      self.drain();
+    }
+
+    *self.clock.lock().expect("Failed to lock event bus clock") = until;
+  }
+
+  /// Handle queued events until the queue is empty, including any events
+  /// posted by their listeners, and get how many were handled.  The calling
+  /// thread waits for each event while its listeners run on the emitter's
+  /// threads.  Returns immediately if another thread is already draining
+  /// the queue, which leaves the events to that thread, so an inline post
+  /// racing another thread's post isn't handled by the time it returns.
+  /// Must not be called from a listener
+  pub fn drain(&self) -> usize {
+    let mut handled = 0;
+
+    loop {
+      if let Ok(_draining) = self.draining.try_lock() {
+        while let Ok(dispatch) = self.source.try_recv() {
+          run_dispatch(dispatch, &self.pending);
+          handled += 1;
+        }
+      }
+      else {
+        return handled
+      }
+
+      // An event posted just before the lock was released would otherwise
+      // wait for the next post
+      if self.source.is_empty() {
+        return handled
+      }
+    }
+  }
+
+  /// Block until every event posted so far, and every event posted by their
+  /// listeners, has been handled.  Delayed events that aren't due yet
+  /// aren't waited for.  Unless the bus has a thread pool the
+  /// events are handled on the calling thread.  Must not be called from a
+  /// listener
+  pub fn flush(&self) {
+    match self.mode {
+      DispatchMode::ThreadPool(_) => (),
+      DispatchMode::Ordered | DispatchMode::Inline => {
+        self.drain();
+      }
+    }
+
+    self.pending.wait_until_idle();
+  }
+
+  /// Stop accepting new events, drop any delayed events that aren't due yet,
+  /// and block until the events already posted have been handled.  Must
+  /// not be called from a listener
+  pub fn shutdown(&self) {
+    self.shut_down.store(true, Ordering::SeqCst);
+    self.timer.stop();
+    self.flush();
+    info!("Event bus shut down");
+  }
+}
+
+impl Drop for EventBus {
+  fn drop(&mut self) {
+    let unhandled = self.source.len();
+
+    self.timer.stop();
+
+    match self.mode {
+      DispatchMode::ThreadPool(_) if unhandled > 0 =>
+          info!("Dropping event bus, workers will finish {} events",
+              unhandled),
+      DispatchMode::Ordered | DispatchMode::Inline if unhandled > 0 =>
+          warn!("Dropping event bus with {} unhandled events", unhandled),
+      _ => info!("Dropping event bus")
+    }
+  }
+}
+
+#[cfg(test)]
+use event::Layout;
+
+#[cfg(test)]
+struct LayoutLog {
+  bus: Arc<EventBus>,
+  widths: Mutex<Vec<i64>>
+}
+
+#[cfg(test)]
+impl EventListener<Layout> for LayoutLog {
+  fn on_event(&self, event: &Layout) {
+    self.widths.lock().unwrap().push(event.width);
+
+    if event.width == 1 {
+      self.bus.post(Layout{width: 2, height: 0});
+    }
+  }
+}
+
+#[cfg(test)]
+fn log_layouts(bus: &Arc<EventBus>) -> (Arc<LayoutLog>, ListenerRegistration) {
+  let log = Arc::new(LayoutLog {
+    bus: bus.clone(),
+    widths: Mutex::new(Vec::new())
+  });
+
+  let registration = bus.register(&log);
+
+  (log, registration)
+}
+
+#[test]
+fn test_ordered_waits_for_drain() {
+  let bus = EventBus::with_mode(DispatchMode::Ordered);
+  let (log, _registration) = log_layouts(&bus);
+
+  bus.post(Layout{width: 1, height: 0});
+  bus.post(Layout{width: 3, height: 0});
+
+  assert!(log.widths.lock().unwrap().is_empty());
+  assert_eq!(bus.drain(), 3);
+  assert_eq!(*log.widths.lock().unwrap(), vec![1, 3, 2]);
+}
+
+#[test]
+fn test_inline_handles_before_post_returns() {
+  let bus = EventBus::with_mode(DispatchMode::Inline);
+  let (log, _registration) = log_layouts(&bus);
+
+  bus.post(Layout{width: 1, height: 0});
+  assert_eq!(*log.widths.lock().unwrap(), vec![1, 2]);
+
+  bus.post(Layout{width: 3, height: 0});
+  assert_eq!(*log.widths.lock().unwrap(), vec![1, 2, 3]);
+  assert_eq!(bus.drain(), 0);
+}
+
+#[test]
+fn test_flush_waits_for_thread_pool() {
+  let bus = EventBus::new();
+  let (log, _registration) = log_layouts(&bus);
+
+  bus.post(Layout{width: 1, height: 0});
+  bus.post(Layout{width: 3, height: 0});
+  bus.flush();
+
+  let mut widths = log.widths.lock().unwrap().clone();
+  widths.sort();
+
+  assert_eq!(widths, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_delayed_posts_wait_for_clock() {
+  let bus = EventBus::with_mode(DispatchMode::Inline);
+  let (log, _registration) = log_layouts(&bus);
+
+  bus.post_after(Duration::from_millis(20), Layout{width: 3, height: 0});
+  bus.post_after(Duration::from_millis(10), Layout{width: 1, height: 0});
+
+  bus.advance_clock(Duration::from_millis(5));
+  assert!(log.widths.lock().unwrap().is_empty());
+
+  bus.advance_clock(Duration::from_millis(10));
+  assert_eq!(*log.widths.lock().unwrap(), vec![1, 2]);
+
+  bus.advance_clock(Duration::from_millis(10));
+  assert_eq!(*log.widths.lock().unwrap(), vec![1, 2, 3]);
+}
+
+#[cfg(test)]
+use std::sync::mpsc;
+
+/// Sends the width of each layout, and when it was handled, to a test
+#[cfg(test)]
+struct LayoutSender(Mutex<mpsc::Sender<(i64, Instant)>>);
+
+#[cfg(test)]
+impl EventListener<Layout> for LayoutSender {
+  fn on_event(&self, event: &Layout) {
+    let _ = self.0.lock().unwrap().send((event.width, Instant::now()));
+  }
+}
+
+#[test]
+fn test_delayed_posts_run_on_thread_pool() {
+  let bus = EventBus::new();
+  let (sender, receiver) = mpsc::channel();
+  let listener = Arc::new(LayoutSender(Mutex::new(sender)));
+  let _registration = bus.register(&listener);
+
+  let posted = Instant::now();
+  bus.post_after(Duration::from_millis(10), Layout{width: 3, height: 0});
+
+  let (width, handled) = receiver.recv_timeout(Duration::from_secs(5))
+      .expect("Delayed event was never posted");
+
+  assert_eq!(width, 3);
+  assert!(handled.duration_since(posted) >= Duration::from_millis(10));
+}
+
+#[cfg(test)]
+struct PanickingListener;
+
+#[cfg(test)]
+impl EventListener<Layout> for PanickingListener {
+  fn on_event(&self, _: &Layout) {
+    panic!("Listener failed");
+  }
+}
+
+#[test]
+fn test_panicking_listener_is_isolated() {
+  let bus = EventBus::with_mode(DispatchMode::Inline);
+  let panicking = Arc::new(PanickingListener);
+  let _panicking_registration = bus.register(&panicking);
+  let (log, _registration) = log_layouts(&bus);
+
+  bus.post(Layout{width: 3, height: 0});
+  bus.post(Layout{width: 4, height: 0});
+
+  assert_eq!(*log.widths.lock().unwrap(), vec![3, 4]);
+
+  let metrics = bus.metrics();
+
+  assert_eq!(*metrics.events_handled(), 2);
+  assert_eq!(*metrics.listener_calls(), 4);
+  assert_eq!(*metrics.listener_panics(), 2);
+  assert_eq!(*metrics.queue_depth(), 0);
+}
+
+#[test]
+fn test_shutdown_ignores_later_posts() {
+  let bus = EventBus::with_mode(DispatchMode::Ordered);
+  let (log, _registration) = log_layouts(&bus);
+
+  bus.post(Layout{width: 3, height: 0});
+  bus.shutdown();
+  bus.post(Layout{width: 4, height: 0});
+
+  assert_eq!(bus.drain(), 0);
+  assert_eq!(*log.widths.lock().unwrap(), vec![3]);
+}
+
+#[test]
+fn test_tracing_records_posts_and_listeners() {
+  let bus = EventBus::with_mode(DispatchMode::Inline);
+  let (_log, _registration) = log_layouts(&bus);
+
+  bus.post(Layout{width: 3, height: 0});
+
+  let tracer = bus.start_tracing();
+
+  bus.post(Layout{width: 1, height: 0});
+
+  assert!(bus.stop_tracing().is_some());
+  bus.post(Layout{width: 4, height: 0});
+
+  let records = tracer.records();
+  let payloads : Vec<&str> = records.iter()
+      .map(|record| record.payload().as_str())
+      .collect();
+
+  assert_eq!(payloads, vec![
+      "Layout { width: 1, height: 0 }",
+      "Layout { width: 2, height: 0 }"]);
+  assert!(records.iter().all(|record| {
+    record.handled().is_some() && record.listeners().len() == 1
+  }));
+
+  assert_eq!(tracer.to_json_lines().lines().count(), 2);
+  assert!(tracer.to_chrome_trace().starts_with("{\"traceEvents\":["));
+}
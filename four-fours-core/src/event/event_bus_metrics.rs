@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+/// How busy an event bus is and how long its listeners take
+#[derive(Debug, Clone, Default, Getters)]
+pub struct EventBusMetrics {
+  /// Events posted but not yet picked up to be handled
+  #[get = "pub"] queue_depth: usize,
+  #[get = "pub"] max_queue_depth: usize,
+  #[get = "pub"] events_handled: usize,
+  /// Events the emitter failed to deliver to all their listeners
+  #[get = "pub"] event_errors: usize,
+  #[get = "pub"] listener_calls: usize,
+  #[get = "pub"] listener_panics: usize,
+  #[get = "pub"] total_listener_time: Duration,
+  #[get = "pub"] max_listener_time: Duration
+}
+
+impl EventBusMetrics {
+
+  pub fn mean_listener_time(&self) -> Duration {
+    if self.listener_calls == 0 {
+      Duration::default()
+    }
+    else {
+      self.total_listener_time / self.listener_calls as u32
+    }
+  }
+
+  pub(crate) fn set_queue_depth(&mut self, queue_depth: usize) {
+    self.queue_depth = queue_depth;
+    self.max_queue_depth = self.max_queue_depth.max(queue_depth);
+  }
+
+  pub(crate) fn record_event(&mut self, failed: bool) {
+    self.events_handled += 1;
+
+    if failed {
+      self.event_errors += 1;
+    }
+  }
+
+  pub(crate) fn record_listener(&mut self, elapsed: Duration, panicked: bool) {
+    self.listener_calls += 1;
+    self.total_listener_time += elapsed;
+    self.max_listener_time = self.max_listener_time.max(elapsed);
+
+    if panicked {
+      self.listener_panics += 1;
+    }
+  }
+}
+
+#[test]
+fn test_mean_listener_time() {
+  let mut metrics = EventBusMetrics::default();
+
+  assert_eq!(metrics.mean_listener_time(), Duration::default());
+
+  metrics.record_listener(Duration::from_millis(10), false);
+  metrics.record_listener(Duration::from_millis(30), true);
+
+  assert_eq!(metrics.mean_listener_time(), Duration::from_millis(20));
+  assert_eq!(*metrics.max_listener_time(), Duration::from_millis(30));
+  assert_eq!(*metrics.listener_panics(), 1);
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::thread;
+use std::time::Instant;
+
+use event::EventBus;
+
+/// Posts a delayed event on the bus it's given once the event is due
+pub(crate) type ScheduledPost = Box<Fn(&EventBus) + Send>;
+
+/// Delayed posts keyed by when they're due, then by the order they were
+/// scheduled in so posts due at the same time keep their order
+#[derive(Default)]
+struct Schedule {
+  posts: BTreeMap<(Instant, usize), ScheduledPost>,
+  next_seq: usize,
+  stopped: bool
+}
+
+/// Holds events posted with a delay until they're due.  On a bus with a
+/// thread pool a single timer thread posts them, otherwise they're posted
+/// as the bus's clock is advanced
+#[derive(Default)]
+pub(crate) struct EventTimer {
+  schedule: Mutex<Schedule>,
+  changed: Condvar
+}
+
+impl EventTimer {
+
+  /// Hold the given post until the given time
+  pub fn schedule(&self, due: Instant, post: ScheduledPost) {
+    let mut schedule = self.schedule.lock()
+        .expect("Failed to lock event timer schedule");
+    let seq = schedule.next_seq;
+
+    schedule.next_seq += 1;
+    schedule.posts.insert((due, seq), post);
+
+    self.changed.notify_all();
+  }
+
+  /// Take the earliest post if it's due at or before the given time, along
+  /// with when it was due
+  pub fn take_due(&self, now: Instant) -> Option<(Instant, ScheduledPost)> {
+    let mut schedule = self.schedule.lock()
+        .expect("Failed to lock event timer schedule");
+
+    let key = match schedule.posts.keys().next() {
+      Some(&key) if key.0 <= now => key,
+      _ => return None
+    };
+
+    schedule.posts.remove(&key).map(|post| (key.0, post))
+  }
+
+  /// Drop every post still waiting and stop the timer thread, if any
+  pub fn stop(&self) {
+    let mut schedule = self.schedule.lock()
+        .expect("Failed to lock event timer schedule");
+
+    schedule.stopped = true;
+    schedule.posts.clear();
+
+    self.changed.notify_all();
+  }
+
+  /// Block until the earliest post is due and take it.  Returns nothing
+  /// once the timer has been stopped
+  fn wait_for_due(&self) -> Option<ScheduledPost> {
+    let mut schedule = self.schedule.lock()
+        .expect("Failed to lock event timer schedule");
+
+    loop {
+      if schedule.stopped {
+        return None
+      }
+
+      let now = Instant::now();
+      let next = schedule.posts.keys().next().cloned();
+
+      schedule = match next {
+        Some(key) if key.0 <= now => return schedule.posts.remove(&key),
+        Some(key) => self.changed
+            .wait_timeout(schedule, key.0.duration_since(now))
+            .expect("Failed to wait for event timer")
+            .0,
+        None => self.changed.wait(schedule)
+            .expect("Failed to wait for event timer")
+      };
+    }
+  }
+
+  /// Start the thread that posts each event on the given bus once it's
+  /// due.  The thread only holds the bus while posting, and stops once the
+  /// timer is stopped or the bus is gone
+  pub fn spawn(this: &Arc<EventTimer>, bus: Weak<EventBus>) {
+    let timer = this.clone();
+
+    let spawned = thread::Builder::new()
+        .name(String::from("event-timer"))
+        .spawn(move || {
+          while let Some(post) = timer.wait_for_due() {
+            match bus.upgrade() {
+              Some(bus) => post(&bus),
+              None => break
+            }
+          }
+
+          debug!("Event timer stopped");
+        });
+
+    if let Err(e) = spawned {
+      error!("Failed to start event timer thread: {}", e);
+    }
+  }
+}
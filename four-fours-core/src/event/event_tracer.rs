@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use event::{FourFoursEvent, ListenerSpan, TraceRecord};
+use util::json_string;
+
+/// Records every event posted on an event bus while tracing, along with the
+/// listeners that ran for it and how long they took
+pub struct EventTracer {
+  start: Instant,
+  records: Mutex<Vec<TraceRecord>>
+}
+
+impl EventTracer {
+
+  pub fn new() -> EventTracer {
+    EventTracer {
+      start: Instant::now(),
+      records: Mutex::new(Vec::new())
+    }
+  }
+
+  /// Get a copy of everything traced so far, in the order it was posted
+  pub fn records(&self) -> Vec<TraceRecord> {
+    self.records.lock().expect("Failed to lock trace records").clone()
+  }
+
+  pub(crate) fn record_post(&self,
+      seq: usize,
+      event_type: FourFoursEvent,
+      payload: String) {
+    let record = TraceRecord::new(
+        seq,
+        event_type,
+        payload,
+        current_thread_name(),
+        self.since_start(Instant::now()));
+
+    self.records.lock().expect("Failed to lock trace records").push(record);
+  }
+
+  pub(crate) fn record_handled(&self, seq: usize, start: Instant) {
+    let end = Instant::now();
+    let (start, end) = (self.since_start(start), self.since_start(end));
+
+    self.with_record(seq, |record| {
+      record.set_handled(current_thread_name(), start, end)
+    });
+  }
+
+  pub(crate) fn record_listener(&self,
+      seq: usize,
+      listener_id: usize,
+      start: Instant,
+      panicked: bool) {
+    let span = ListenerSpan::new(
+        listener_id,
+        current_thread_name(),
+        self.since_start(start),
+        start.elapsed(),
+        panicked);
+
+    self.with_record(seq, |record| record.add_listener(span));
+  }
+
+  /// Events posted before tracing started aren't recorded, so their
+  /// listeners are ignored
+  fn with_record<F>(&self, seq: usize, update: F)
+      where F: FnOnce(&mut TraceRecord) {
+    let mut records = self.records.lock()
+        .expect("Failed to lock trace records");
+
+    if let Some(record) = records.iter_mut().rev()
+        .find(|record| *record.seq() == seq) {
+      update(record);
+    }
+  }
+
+  fn since_start(&self, instant: Instant) -> Duration {
+    if instant > self.start {
+      instant - self.start
+    }
+    else {
+      Duration::default()
+    }
+  }
+
+  /// One JSON object per event
+  pub fn to_json_lines(&self) -> String {
+    self.records().iter()
+        .map(|record| {
+          let handled = match *record.handled() {
+            Some((ref thread, start, end)) => format!(
+                "\"handled_by\":{},\"handled_us\":{},\"finished_us\":{}",
+                json_string(thread),
+                micros(start),
+                micros(end)),
+            None => "\"handled_by\":null,\"handled_us\":null,\
+                \"finished_us\":null".to_string()
+          };
+
+          let listeners : Vec<String> = record.listeners().iter()
+              .map(|span| format!(
+                  "{{\"listener\":{},\"thread\":{},\"start_us\":{},\
+                      \"duration_us\":{},\"panicked\":{}}}",
+                  span.listener_id(),
+                  json_string(span.thread()),
+                  micros(*span.start()),
+                  micros(*span.duration()),
+                  span.panicked()))
+              .collect();
+
+          format!("{{\"seq\":{},\"event\":{},\"payload\":{},\
+                  \"posted_by\":{},\"posted_us\":{},{},\"listeners\":[{}]}}\n",
+              record.seq(),
+              json_string(&format!("{:?}", record.event_type())),
+              json_string(record.payload()),
+              json_string(record.posted_by()),
+              micros(*record.posted_at()),
+              handled,
+              listeners.join(","))
+        })
+        .collect()
+  }
+
+  /// The Chrome trace event format, which can be opened in chrome://tracing
+  /// or Perfetto.  Each event is drawn as a span on the thread that handled
+  /// it, with an arrow from where it was posted and its listeners' spans on
+  /// their threads
+  pub fn to_chrome_trace(&self) -> String {
+    let mut thread_ids : HashMap<String, usize> = HashMap::new();
+    let mut trace_events : Vec<String> = Vec::new();
+
+    let mut tid = |thread: &str, trace_events: &mut Vec<String>| {
+      let next_id = thread_ids.len() + 1;
+
+      *thread_ids.entry(thread.to_string()).or_insert_with(|| {
+        trace_events.push(format!(
+            "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":1,\
+                \"tid\":{},\"args\":{{\"name\":{}}}}}",
+            next_id,
+            json_string(thread)));
+        next_id
+      })
+    };
+
+    for record in self.records() {
+      let name = json_string(&format!("{:?}", record.event_type()));
+      let poster = tid(record.posted_by().as_str(), &mut trace_events);
+
+      trace_events.push(format!(
+          "{{\"name\":{},\"cat\":\"post\",\"ph\":\"i\",\"s\":\"t\",\
+              \"ts\":{},\"pid\":1,\"tid\":{},\
+              \"args\":{{\"seq\":{},\"payload\":{}}}}}",
+          name,
+          micros(*record.posted_at()),
+          poster,
+          record.seq(),
+          json_string(record.payload())));
+
+      if let Some((ref thread, start, end)) = *record.handled() {
+        let handler = tid(thread.as_str(), &mut trace_events);
+
+        trace_events.push(format!(
+            "{{\"name\":{},\"cat\":\"queue\",\"ph\":\"s\",\"id\":{},\
+                \"ts\":{},\"pid\":1,\"tid\":{}}}",
+            name, record.seq(), micros(*record.posted_at()), poster));
+        trace_events.push(format!(
+            "{{\"name\":{},\"cat\":\"queue\",\"ph\":\"f\",\"bp\":\"e\",\
+                \"id\":{},\"ts\":{},\"pid\":1,\"tid\":{}}}",
+            name, record.seq(), micros(start), handler));
+        trace_events.push(format!(
+            "{{\"name\":{},\"cat\":\"event\",\"ph\":\"X\",\"ts\":{},\
+                \"dur\":{},\"pid\":1,\"tid\":{},\"args\":{{\"seq\":{}}}}}",
+            name,
+            micros(start),
+            micros(end - start),
+            handler,
+            record.seq()));
+      }
+
+      for span in record.listeners() {
+        let listener_thread = tid(span.thread().as_str(), &mut trace_events);
+
+        trace_events.push(format!(
+            "{{\"name\":\"listener {}\",\"cat\":\"listener\",\"ph\":\"X\",\
+                \"ts\":{},\"dur\":{},\"pid\":1,\"tid\":{},\
+                \"args\":{{\"seq\":{},\"event\":{},\"panicked\":{}}}}}",
+            span.listener_id(),
+            micros(*span.start()),
+            micros(*span.duration()),
+            listener_thread,
+            record.seq(),
+            name,
+            span.panicked()));
+      }
+    }
+
+    format!("{{\"traceEvents\":[\n{}\n]}}\n", trace_events.join(",\n"))
+  }
+
+  /// Write the trace to the file at the given path, as JSON lines if the
+  /// path ends in `.jsonl` and in the Chrome trace format otherwise
+  pub fn save(&self, path: &str) -> io::Result<()> {
+    if path.ends_with(".jsonl") {
+      fs::write(path, self.to_json_lines())
+    }
+    else {
+      fs::write(path, self.to_chrome_trace())
+    }
+  }
+}
+
+impl Default for EventTracer {
+  fn default() -> EventTracer {
+    EventTracer::new()
+  }
+}
+
+fn current_thread_name() -> String {
+  let current = thread::current();
+
+  match current.name() {
+    Some(name) => name.to_string(),
+    None => format!("{:?}", current.id())
+  }
+}
+
+fn micros(duration: Duration) -> u64 {
+  duration.as_secs() * 1_000_000 + u64::from(duration.subsec_micros())
+}
@@ -17,18 +17,38 @@ macro_rules! define_events {
       fn into(self) -> $events_name { $events_name::$e }
     }
 
+    impl TypedEvent for $e {
+      fn event_type() -> $events_name { $events_name::$e }
+    }
+
     )*
   }
 }
 
+use event::TypedEvent;
+
 define_events!(FourFoursEvent,
     LoadResources{},
-    StartGame{ pub new: bool },
+    StartGame{
+      pub new: bool,
+      pub daily: bool,
+    },
     Layout{
       pub width: i64,
       pub height: i64,
     },
-    Evaluate{}
+    Evaluate{},
+    EvaluatePreview{},
+    AnimationFrame{},
+    PauseGame{},
+    ResumeGame{},
+    RestartGame{},
+    NextPuzzle{},
+    QuitToMainMenu{},
+    EnterBackground{},
+    EnterForeground{},
+    LowMemory{},
+    Terminate{}
 );
 
 
@@ -1,9 +1,19 @@
 pub use self::events::*;
 pub use self::event_bus::EventBus;
+pub use self::dispatch_mode::DispatchMode;
+pub use self::event_bus_metrics::EventBusMetrics;
+pub use self::event_tracer::EventTracer;
+pub use self::trace_record::{ListenerSpan, TraceRecord};
 pub use self::listener_registration::ListenerRegistration;
 pub use self::event_listener::EventListener;
+pub use self::typed_event::TypedEvent;
 
 mod events;
 mod event_bus;
+mod dispatch_mode;
+mod event_bus_metrics;
+mod event_tracer;
+mod trace_record;
 mod event_listener;
-mod listener_registration;
\ No newline at end of file
+mod listener_registration;
+mod typed_event;
\ No newline at end of file
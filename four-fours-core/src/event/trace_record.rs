@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use event::FourFoursEvent;
+
+/// One run of a listener for a traced event
+#[derive(Debug, Clone, Getters)]
+pub struct ListenerSpan {
+  #[get = "pub"] listener_id: usize,
+  #[get = "pub"] thread: String,
+  /// When the listener started, relative to the start of the trace
+  #[get = "pub"] start: Duration,
+  #[get = "pub"] duration: Duration,
+  #[get = "pub"] panicked: bool
+}
+
+impl ListenerSpan {
+  pub fn new(listener_id: usize,
+      thread: String,
+      start: Duration,
+      duration: Duration,
+      panicked: bool) -> ListenerSpan {
+    ListenerSpan {
+      listener_id: listener_id,
+      thread: thread,
+      start: start,
+      duration: duration,
+      panicked: panicked
+    }
+  }
+}
+
+/// A traced event and the listeners that ran for it.  Times are relative to
+/// the start of the trace
+#[derive(Debug, Clone, Getters)]
+pub struct TraceRecord {
+  #[get = "pub"] seq: usize,
+  #[get = "pub"] event_type: FourFoursEvent,
+  #[get = "pub"] payload: String,
+  #[get = "pub"] posted_by: String,
+  #[get = "pub"] posted_at: Duration,
+  /// Which thread handled the event and when it started and finished, once
+  /// it has been handled
+  #[get = "pub"] handled: Option<(String, Duration, Duration)>,
+  #[get = "pub"] listeners: Vec<ListenerSpan>
+}
+
+impl TraceRecord {
+
+  pub fn new(seq: usize,
+      event_type: FourFoursEvent,
+      payload: String,
+      posted_by: String,
+      posted_at: Duration) -> TraceRecord {
+    TraceRecord {
+      seq: seq,
+      event_type: event_type,
+      payload: payload,
+      posted_by: posted_by,
+      posted_at: posted_at,
+      handled: None,
+      listeners: Vec::new()
+    }
+  }
+
+  pub(crate) fn set_handled(&mut self,
+      thread: String,
+      start: Duration,
+      end: Duration) {
+    self.handled = Some((thread, start, end));
+  }
+
+  pub(crate) fn add_listener(&mut self, span: ListenerSpan) {
+    self.listeners.push(span);
+  }
+}
@@ -0,0 +1,10 @@
+use std::fmt::Debug;
+
+use event::FourFoursEvent;
+
+/// A payload that can be posted on the event bus.  Implemented for each
+/// payload by `define_events!` so the kind of event is known from the
+/// payload's type alone
+pub trait TypedEvent : Send + Clone + Debug + Into<FourFoursEvent> + 'static {
+  fn event_type() -> FourFoursEvent;
+}
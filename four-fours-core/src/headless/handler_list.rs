@@ -0,0 +1,103 @@
+use std::sync::{Arc, Mutex};
+
+use ui::HandlerRegistration;
+
+/// Handlers added to a headless view or sprite.  Handlers are called
+/// without holding the list's lock, so they are free to add or remove
+/// handlers themselves
+pub struct HandlerList<H> {
+  next_id: Arc<Mutex<usize>>,
+  handlers: Arc<Mutex<Vec<(usize, Arc<H>)>>>
+}
+
+impl <H> Clone for HandlerList<H> {
+  fn clone(&self) -> HandlerList<H> {
+    HandlerList {
+      next_id: self.next_id.clone(),
+      handlers: self.handlers.clone()
+    }
+  }
+}
+
+impl <H> Default for HandlerList<H> {
+  fn default() -> HandlerList<H> {
+    HandlerList {
+      next_id: Arc::new(Mutex::new(0)),
+      handlers: Arc::new(Mutex::new(Vec::default()))
+    }
+  }
+}
+
+impl <H: 'static> HandlerList<H> {
+
+  pub fn add(&self, handler: H) -> HeadlessRegistration {
+    let id = {
+      let mut next_id = self.next_id.lock()
+          .expect("Failed to lock next handler id");
+      *next_id += 1;
+      *next_id
+    };
+
+    self.handlers.lock().expect("Failed to lock handlers")
+        .push((id, Arc::new(handler)));
+
+    let handlers = self.handlers.clone();
+
+    HeadlessRegistration::new(move || {
+      handlers.lock().expect("Failed to lock handlers")
+          .retain(|&(handler_id, _)| handler_id != id);
+    })
+  }
+
+  pub fn len(&self) -> usize {
+    self.handlers.lock().expect("Failed to lock handlers").len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Call the given function with each of the handlers in the order they
+  /// were added
+  pub fn each<F>(&self, f: F) where F: Fn(&H) {
+    let handlers : Vec<Arc<H>> = self.handlers.lock()
+        .expect("Failed to lock handlers")
+        .iter()
+        .map(|&(_, ref handler)| handler.clone())
+        .collect();
+
+    for handler in handlers {
+      f(&*handler);
+    }
+  }
+}
+
+/// Registration that removes a handler from a `HandlerList`
+pub struct HeadlessRegistration(Box<Fn()>);
+
+impl HeadlessRegistration {
+  pub fn new<F>(deregister: F) -> HeadlessRegistration
+      where F: Fn() + 'static {
+    HeadlessRegistration(Box::new(deregister))
+  }
+}
+
+impl HandlerRegistration for HeadlessRegistration {
+  fn deregister(&self) {
+    (self.0)()
+  }
+}
+
+#[test]
+fn test_add_and_deregister() {
+  let list : HandlerList<usize> = HandlerList::default();
+  let first = list.add(1);
+  let _second = list.add(2);
+
+  first.deregister();
+
+  let seen = Mutex::new(Vec::new());
+  list.each(|h| seen.lock().unwrap().push(*h));
+
+  assert_eq!(*seen.lock().unwrap(), vec![2]);
+}
@@ -0,0 +1,60 @@
+use std::sync::{Arc, Mutex};
+
+use headless::{HandlerList, HeadlessRegistration};
+use ui::{
+  Button,
+  ClickHandler,
+  HasClickHandlers,
+  HasIntValue,
+  HasText,
+  ProgressBar
+};
+
+/// Text area, button or progress bar that keeps its text and value in
+/// memory.  Clones share state
+#[derive(Clone, Default)]
+pub struct HeadlessControl {
+  text: Arc<Mutex<String>>,
+  int_value: Arc<Mutex<i64>>,
+  click_handlers: HandlerList<ClickHandler>
+}
+
+impl HeadlessControl {
+
+  /// Act as though the control was clicked
+  pub fn click(&self) {
+    self.click_handlers.each(|handler| handler.on_click());
+  }
+}
+
+impl HasText for HeadlessControl {
+  fn get_text(&self) -> String {
+    self.text.lock().expect("Failed to lock text").clone()
+  }
+
+  fn set_text(&self, new_text: String) {
+    *self.text.lock().expect("Failed to lock text") = new_text;
+  }
+}
+
+impl HasIntValue for HeadlessControl {
+  fn set_int_value(&self, value: i64) {
+    *self.int_value.lock().expect("Failed to lock int value") = value;
+  }
+
+  fn get_int_value(&self) -> i64 {
+    *self.int_value.lock().expect("Failed to lock int value")
+  }
+}
+
+impl HasClickHandlers for HeadlessControl {
+  type R = HeadlessRegistration;
+
+  fn add_click_handler(&self, handler: ClickHandler) -> HeadlessRegistration {
+    self.click_handlers.add(handler)
+  }
+}
+
+impl Button for HeadlessControl {}
+
+impl ProgressBar for HeadlessControl {}
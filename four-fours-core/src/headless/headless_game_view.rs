@@ -0,0 +1,235 @@
+use std::sync::{Arc, Mutex};
+
+use headless::{
+  HandlerList,
+  HeadlessControl,
+  HeadlessRegistration,
+  HeadlessSprite,
+  HeadlessTexture
+};
+use math::TexElement;
+use model::Point;
+use ui::{
+  DragHandler,
+  GameView,
+  HasDragHandlers,
+  HasKeyHandlers,
+  HasLayoutHandlers,
+  HasTexDisplay,
+  KeyHandler,
+  LayoutHandler,
+  SpriteSource
+};
+
+/// Game view that keeps everything drawn on it in memory and can be sent
+/// input as though a player were using it.  Clones share state
+#[derive(Clone, Default)]
+pub struct HeadlessGameView {
+  sprites: Arc<Mutex<Vec<HeadlessSprite>>>,
+  layout_handlers: HandlerList<LayoutHandler>,
+  drag_handlers: HandlerList<DragHandler>,
+  key_handlers: HandlerList<KeyHandler>,
+
+  tex: Arc<Mutex<Vec<TexElement>>>,
+  tex_ghosted: Arc<Mutex<bool>>,
+
+  goal_text: HeadlessControl,
+  value_text: HeadlessControl,
+  status_text: HeadlessControl,
+  pause_button: HeadlessControl,
+
+  transitions: Arc<Mutex<Vec<String>>>
+}
+
+impl HeadlessGameView {
+
+  /// Every sprite created through this view, oldest first
+  pub fn sprites(&self) -> Vec<HeadlessSprite> {
+    self.sprites.lock().expect("Failed to lock sprites").clone()
+  }
+
+  /// Sprites created through this view that are shown and not removed
+  pub fn visible_sprites(&self) -> Vec<HeadlessSprite> {
+    self.sprites().into_iter()
+        .filter(|sprite| {
+          let state = sprite.state();
+          state.visible && !state.removed
+        })
+        .collect()
+  }
+
+  pub fn tex(&self) -> Vec<TexElement> {
+    self.tex.lock().expect("Failed to lock tex").clone()
+  }
+
+  pub fn is_tex_ghosted(&self) -> bool {
+    *self.tex_ghosted.lock().expect("Failed to lock tex ghosted")
+  }
+
+  /// Names of the views this view has transitioned to, oldest first
+  pub fn transitions(&self) -> Vec<String> {
+    self.transitions.lock().expect("Failed to lock transitions").clone()
+  }
+
+  pub fn layout(&self, width: i64, height: i64) {
+    self.layout_handlers.each(|handler| handler.on_layout(width, height));
+  }
+
+  pub fn drag_start(&self, x: f64, y: f64) {
+    self.drag_handlers.each(|handler| handler.on_drag_start(x, y, 0., 0.));
+  }
+
+  pub fn drag_move(&self, x: f64, y: f64) {
+    self.drag_handlers.each(|handler| handler.on_drag_move(x, y, 0., 0.));
+  }
+
+  pub fn drag_end(&self, x: f64, y: f64) {
+    self.drag_handlers.each(|handler| handler.on_drag_end(x, y, 0., 0.));
+  }
+
+  /// Press at one point, move to another in the given number of steps and
+  /// release there
+  pub fn drag(&self, from: &Point, to: &Point, steps: usize) {
+    self.drag_start(from.x, from.y);
+
+    for step in 1..(steps + 1) {
+      let t = step as f64 / steps as f64;
+
+      self.drag_move(
+          from.x + (to.x - from.x) * t,
+          from.y + (to.y - from.y) * t);
+    }
+
+    self.drag_end(to.x, to.y);
+  }
+
+  /// Press and release at the given point.  Like the native views, the press
+  /// goes to the view's drag handlers and the topmost sprite under the point
+  /// with click handlers is clicked once the press is released
+  pub fn tap(&self, x: f64, y: f64) {
+    self.drag_start(x, y);
+    self.drag_end(x, y);
+    self.click(x, y);
+  }
+
+  /// Click the topmost sprite under the given point with click handlers,
+  /// without a press reaching the view's drag handlers first
+  pub fn click(&self, x: f64, y: f64) {
+    let point = Point::new(x, y);
+
+    let clicked = self.sprites().into_iter()
+        .rev()
+        .find(|sprite| sprite.has_click_handlers() && sprite.is_hit(&point));
+
+    if let Some(sprite) = clicked {
+      sprite.click();
+    }
+  }
+
+  pub fn key(&self, code: i64) {
+    self.key_handlers.each(|handler| handler.on_key(code));
+  }
+
+  /// Send a key press for each character of the given text
+  pub fn type_text(&self, text: &str) {
+    for c in text.chars() {
+      self.key(c as i64);
+    }
+  }
+}
+
+impl SpriteSource for HeadlessGameView {
+  type T = HeadlessTexture;
+  type S = HeadlessSprite;
+
+  fn create_sprite(&self) -> HeadlessSprite {
+    let sprite = HeadlessSprite::default();
+
+    self.sprites.lock().expect("Failed to lock sprites").push(sprite.clone());
+
+    sprite
+  }
+}
+
+impl HasLayoutHandlers for HeadlessGameView {
+  type R = HeadlessRegistration;
+
+  fn add_layout_handler(&self, handler: LayoutHandler)
+      -> HeadlessRegistration {
+    self.layout_handlers.add(handler)
+  }
+}
+
+impl HasDragHandlers for HeadlessGameView {
+  type R = HeadlessRegistration;
+
+  fn add_drag_handler(&self, handler: DragHandler) -> HeadlessRegistration {
+    self.drag_handlers.add(handler)
+  }
+}
+
+impl HasKeyHandlers for HeadlessGameView {
+  type R = HeadlessRegistration;
+
+  fn add_key_handler(&self, handler: KeyHandler) -> HeadlessRegistration {
+    self.key_handlers.add(handler)
+  }
+}
+
+impl HasTexDisplay for HeadlessGameView {
+  fn clear_tex(&self) {
+    self.tex.lock().expect("Failed to lock tex").clear();
+  }
+
+  fn add_tex_glyph(&self,
+      codepoint: i64,
+      left: f64,
+      baseline: f64,
+      size: f64) {
+    self.tex.lock().expect("Failed to lock tex").push(TexElement::Glyph {
+      codepoint: codepoint as u32,
+      left: left,
+      baseline: baseline,
+      size: size
+    });
+  }
+
+  fn add_tex_rule(&self, left: f64, top: f64, width: f64, height: f64) {
+    self.tex.lock().expect("Failed to lock tex").push(TexElement::Rule {
+      left: left,
+      top: top,
+      width: width,
+      height: height
+    });
+  }
+
+  fn set_tex_ghosted(&self, ghosted: bool) {
+    *self.tex_ghosted.lock().expect("Failed to lock tex ghosted") = ghosted;
+  }
+}
+
+impl GameView for HeadlessGameView {
+  type TA = HeadlessControl;
+  type B = HeadlessControl;
+
+  fn get_goal_text(&self) -> HeadlessControl {
+    self.goal_text.clone()
+  }
+
+  fn get_value_text(&self) -> HeadlessControl {
+    self.value_text.clone()
+  }
+
+  fn get_status_text(&self) -> HeadlessControl {
+    self.status_text.clone()
+  }
+
+  fn get_pause_button(&self) -> HeadlessControl {
+    self.pause_button.clone()
+  }
+
+  fn transition_to_pause_view(&self) {
+    self.transitions.lock().expect("Failed to lock transitions")
+        .push(String::from("pause"));
+  }
+}
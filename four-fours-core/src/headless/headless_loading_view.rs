@@ -0,0 +1,32 @@
+use std::sync::{Arc, Mutex};
+
+use headless::HeadlessControl;
+use ui::LoadingView;
+
+/// Loading view that records its progress and transitions in memory
+#[derive(Clone, Default)]
+pub struct HeadlessLoadingView {
+  progress: HeadlessControl,
+  transitions: Arc<Mutex<Vec<String>>>
+}
+
+impl HeadlessLoadingView {
+
+  /// Names of the views this view has transitioned to, oldest first
+  pub fn transitions(&self) -> Vec<String> {
+    self.transitions.lock().expect("Failed to lock transitions").clone()
+  }
+}
+
+impl LoadingView for HeadlessLoadingView {
+  type P = HeadlessControl;
+
+  fn get_progress_indicator(&self) -> HeadlessControl {
+    self.progress.clone()
+  }
+
+  fn transition_to_main_menu_view(&self) {
+    self.transitions.lock().expect("Failed to lock transitions")
+        .push(String::from("main_menu"));
+  }
+}
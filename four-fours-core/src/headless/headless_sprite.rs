@@ -0,0 +1,137 @@
+use std::sync::{Arc, Mutex};
+
+use headless::{HandlerList, HeadlessRegistration, HeadlessTexture};
+use model::{Point, Rect};
+use ui::{
+  ClickHandler,
+  DragHandler,
+  HasClickHandlers,
+  HasDragHandlers,
+  HasMutableLocation,
+  HasMutableSize,
+  HasMutableVisibility,
+  Sprite
+};
+
+/// Everything last set on a headless sprite
+#[derive(Debug, Clone, Default)]
+pub struct SpriteState {
+  pub rect: Rect,
+  pub visible: bool,
+  pub texture: Option<HeadlessTexture>,
+  pub tint: (f64, f64, f64, f64),
+  pub alpha: f64,
+  pub removed: bool
+}
+
+/// Sprite that records what is done to it in memory.  Clones share state,
+/// so a test can keep a copy of any sprite a presenter creates
+#[derive(Clone)]
+pub struct HeadlessSprite {
+  state: Arc<Mutex<SpriteState>>,
+  click_handlers: HandlerList<ClickHandler>,
+  drag_handlers: HandlerList<DragHandler>
+}
+
+impl Default for HeadlessSprite {
+  fn default() -> HeadlessSprite {
+    HeadlessSprite {
+      state: Arc::new(Mutex::new(SpriteState {
+        alpha: 1.,
+        .. SpriteState::default()
+      })),
+      click_handlers: HandlerList::default(),
+      drag_handlers: HandlerList::default()
+    }
+  }
+}
+
+impl HeadlessSprite {
+
+  pub fn state(&self) -> SpriteState {
+    self.state.lock().expect("Failed to lock sprite state").clone()
+  }
+
+  /// True if the sprite is shown and covers the given point
+  pub fn is_hit(&self, point: &Point) -> bool {
+    let state = self.state();
+
+    state.visible && !state.removed && state.rect.contains(point)
+  }
+
+  pub fn has_click_handlers(&self) -> bool {
+    !self.click_handlers.is_empty()
+  }
+
+  /// Act as though the sprite was clicked
+  pub fn click(&self) {
+    self.click_handlers.each(|handler| handler.on_click());
+  }
+
+  fn update<F>(&self, f: F) where F: FnOnce(&mut SpriteState) {
+    f(&mut *self.state.lock().expect("Failed to lock sprite state"))
+  }
+}
+
+impl HasMutableSize for HeadlessSprite {
+  fn set_size_animated(&self, width: f64, height: f64, _: f64) {
+    self.update(|state| {
+      state.rect.size.width = width;
+      state.rect.size.height = height;
+    });
+  }
+}
+
+impl HasMutableLocation for HeadlessSprite {
+  fn set_location_animated(&self, left: f64, top: f64, _: f64) {
+    self.update(|state| {
+      state.rect.top_left.x = left;
+      state.rect.top_left.y = top;
+    });
+  }
+}
+
+impl HasMutableVisibility for HeadlessSprite {
+  fn set_visible(&self, visible: bool) {
+    self.update(|state| state.visible = visible);
+  }
+}
+
+impl HasClickHandlers for HeadlessSprite {
+  type R = HeadlessRegistration;
+
+  fn add_click_handler(&self, handler: ClickHandler) -> HeadlessRegistration {
+    self.click_handlers.add(handler)
+  }
+}
+
+impl HasDragHandlers for HeadlessSprite {
+  type R = HeadlessRegistration;
+
+  fn add_drag_handler(&self, handler: DragHandler) -> HeadlessRegistration {
+    self.drag_handlers.add(handler)
+  }
+}
+
+impl Sprite for HeadlessSprite {
+  type T = HeadlessTexture;
+
+  fn set_texture(&self, texture: &HeadlessTexture) {
+    self.update(|state| state.texture = Some(texture.clone()));
+  }
+
+  fn set_tint(&self, red: f64, green: f64, blue: f64, blend_factor: f64) {
+    self.update(|state| state.tint = (red, green, blue, blend_factor));
+  }
+
+  fn set_alpha(&self, alpha: f64) {
+    self.update(|state| state.alpha = alpha);
+  }
+
+  fn propagate_events_to(&self, _: &HeadlessSprite) {
+  }
+
+  fn remove_from_parent(&self) {
+    self.update(|state| state.removed = true);
+  }
+}
@@ -0,0 +1,40 @@
+use std::sync::Mutex;
+
+use headless::{HeadlessTexture, HeadlessTextureLoader};
+use native::{RuntimeResources, SystemView, Textures};
+
+/// System view that keeps saved progress in memory
+#[derive(Default)]
+pub struct HeadlessSystemView {
+  progress: Mutex<String>
+}
+
+impl HeadlessSystemView {
+
+  /// Load all the runtime resources the way the loading presenter would
+  pub fn load_resources(&self) -> RuntimeResources<HeadlessSystemView> {
+    RuntimeResources::new(
+        Textures::new(Box::new(self.get_texture_loader()), &|_| {}))
+  }
+
+  pub fn saved_progress(&self) -> String {
+    self.progress.lock().expect("Failed to lock progress").clone()
+  }
+}
+
+impl SystemView for HeadlessSystemView {
+  type T = HeadlessTexture;
+  type TL = HeadlessTextureLoader;
+
+  fn get_texture_loader(&self) -> HeadlessTextureLoader {
+    HeadlessTextureLoader
+  }
+
+  fn load_progress(&self) -> String {
+    self.saved_progress()
+  }
+
+  fn save_progress(&self, progress: String) {
+    *self.progress.lock().expect("Failed to lock progress") = progress;
+  }
+}
@@ -0,0 +1,70 @@
+use native::{HasIntSize, Texture, TextureLoader};
+
+/// Card textures are tall like a playing card
+const CARD_TEXTURE_WIDTH : i64 = 200;
+const CARD_TEXTURE_HEIGHT : i64 = 300;
+
+const DEFAULT_TEXTURE_SIZE : i64 = 600;
+
+/// A texture that only remembers which image it came from and what part of
+/// the image it covers
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessTexture {
+  pub name: String,
+  pub left: i64,
+  pub top: i64,
+  pub width: i64,
+  pub height: i64
+}
+
+impl HasIntSize for HeadlessTexture {
+  fn get_width(&self) -> i64 {
+    self.width
+  }
+
+  fn get_height(&self) -> i64 {
+    self.height
+  }
+}
+
+impl Texture for HeadlessTexture {
+  fn get_sub_texture(&self,
+      left: i64,
+      top: i64,
+      width: i64,
+      height: i64)
+          -> HeadlessTexture {
+    HeadlessTexture {
+      name: self.name.clone(),
+      left: self.left + left,
+      top: self.top + top,
+      width: width,
+      height: height
+    }
+  }
+}
+
+/// Loads named textures without reading any images
+#[derive(Default)]
+pub struct HeadlessTextureLoader;
+
+impl TextureLoader for HeadlessTextureLoader {
+  type T = HeadlessTexture;
+
+  fn load_texture(&self, name: String) -> HeadlessTexture {
+    let (width, height) = if name.starts_with("Card") {
+      (CARD_TEXTURE_WIDTH, CARD_TEXTURE_HEIGHT)
+    }
+    else {
+      (DEFAULT_TEXTURE_SIZE, DEFAULT_TEXTURE_SIZE)
+    };
+
+    HeadlessTexture {
+      name: name,
+      left: 0,
+      top: 0,
+      width: width,
+      height: height
+    }
+  }
+}
@@ -0,0 +1,18 @@
+//! In-memory implementations of the native and view traits so presenters
+//! can be driven and inspected without a platform behind them
+
+pub use self::handler_list::{HandlerList, HeadlessRegistration};
+pub use self::headless_texture::{HeadlessTexture, HeadlessTextureLoader};
+pub use self::headless_system_view::HeadlessSystemView;
+pub use self::headless_sprite::HeadlessSprite;
+pub use self::headless_control::HeadlessControl;
+pub use self::headless_game_view::HeadlessGameView;
+pub use self::headless_loading_view::HeadlessLoadingView;
+
+mod handler_list;
+mod headless_texture;
+mod headless_system_view;
+mod headless_sprite;
+mod headless_control;
+mod headless_game_view;
+mod headless_loading_view;
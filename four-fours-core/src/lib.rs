@@ -6,8 +6,8 @@
 
 extern crate statrs;
 extern crate num;
+extern crate rex;
 
-extern crate simplelog;
 extern crate itertools;
 
 pub use self::lib_gen::*;
@@ -25,5 +25,11 @@ mod native;
 mod presenter;
 mod util;
 mod math;
+mod animation;
+mod replay;
+mod logging;
+
+#[cfg(test)]
+mod headless;
 
 mod lib_gen;
@@ -0,0 +1,99 @@
+use log::LevelFilter;
+
+/// Which log records to keep.  Written like `RUST_LOG`: a comma separated
+/// list of `target=level` directives, where a directive without a target
+/// sets the level for everything else, e.g.
+/// `info,fourfours::event=debug,fourfours::math=warn`.  A target covers
+/// every module path it's a prefix of, and the longest matching target wins
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogConfig {
+  default_level: LevelFilter,
+  module_levels: Vec<(String, LevelFilter)>
+}
+
+impl LogConfig {
+
+  pub fn new(default_level: LevelFilter) -> LogConfig {
+    LogConfig {
+      default_level: default_level,
+      module_levels: Vec::new()
+    }
+  }
+
+  /// Set the level for the given target and the modules inside it
+  pub fn with_module_level(mut self, target: &str, level: LevelFilter)
+      -> LogConfig {
+    self.module_levels.retain(|&(ref t, _)| t != target);
+    self.module_levels.push((target.to_string(), level));
+    self
+  }
+
+  pub fn parse(config: &str) -> Result<LogConfig, String> {
+    let mut result = LogConfig::default();
+
+    for directive in config.split(',').map(str::trim) {
+      if directive.is_empty() {
+        continue
+      }
+
+      let mut parts = directive.splitn(2, '=');
+      let first = parts.next().unwrap_or("").trim();
+
+      match parts.next() {
+        Some(level) => {
+          let level = level.trim().parse::<LevelFilter>()
+              .map_err(|_| format!("Invalid log level: {}", directive))?;
+          result = result.with_module_level(first, level);
+        },
+        None => {
+          result.default_level = first.parse::<LevelFilter>()
+              .map_err(|_| format!("Invalid log level: {}", directive))?;
+        }
+      }
+    }
+
+    Ok(result)
+  }
+
+  pub fn level_for(&self, target: &str) -> LevelFilter {
+    self.module_levels.iter()
+        .filter(|&&(ref module, _)| is_within(target, module))
+        .max_by_key(|&&(ref module, _)| module.len())
+        .map(|&(_, level)| level)
+        .unwrap_or(self.default_level)
+  }
+
+  /// The most verbose level any target is logged at
+  pub fn max_level(&self) -> LevelFilter {
+    self.module_levels.iter()
+        .map(|&(_, level)| level)
+        .fold(self.default_level, |a, b| a.max(b))
+  }
+}
+
+impl Default for LogConfig {
+  fn default() -> LogConfig {
+    LogConfig::new(LevelFilter::Info)
+  }
+}
+
+fn is_within(target: &str, module: &str) -> bool {
+  target == module || (target.starts_with(module)
+      && target[module.len()..].starts_with("::"))
+}
+
+#[test]
+fn test_parse_module_levels() {
+  let config = LogConfig::parse(
+      "warn, fourfours::event=debug,fourfours::event::event_bus=off").unwrap();
+
+  assert_eq!(config.level_for("fourfours::math"), LevelFilter::Warn);
+  assert_eq!(config.level_for("fourfours::event::events"), LevelFilter::Debug);
+  assert_eq!(config.level_for("fourfours::event::event_bus"), LevelFilter::Off);
+  assert_eq!(config.level_for("fourfours::eventful"), LevelFilter::Warn);
+  assert_eq!(config.max_level(), LevelFilter::Debug);
+
+  assert_eq!(LogConfig::parse(""), Ok(LogConfig::default()));
+  assert!(LogConfig::parse("loud").is_err());
+  assert!(LogConfig::parse("fourfours::math=loud").is_err());
+}
@@ -0,0 +1,31 @@
+pub use self::log_config::LogConfig;
+pub use self::native_logger::{LogSink, NativeLogger};
+
+mod log_config;
+mod native_logger;
+
+use log::{self, SetLoggerError};
+
+lazy_static!{
+  static ref LOGGER : NativeLogger = NativeLogger::default();
+
+  static ref LOGGER_RESULT : Result<(), SetLoggerError>
+      = log::set_logger(&*LOGGER);
+}
+
+/// Send log records allowed by the given configuration to the given sink,
+/// replacing any previous configuration.  Fails if something other than this
+/// crate has already set the process wide logger
+pub fn init_logging(config: LogConfig, sink: LogSink) -> Result<(), String> {
+  let max_level = config.max_level();
+
+  LOGGER.set_target(config, sink);
+
+  match *LOGGER_RESULT {
+    Ok(()) => {
+      log::set_max_level(max_level);
+      Ok(())
+    },
+    Err(ref e) => Err(format!("Failed to set logger: {}", e))
+  }
+}
@@ -0,0 +1,89 @@
+use std::sync::RwLock;
+
+use log::{Level, Log, Metadata, Record};
+
+use logging::LogConfig;
+
+/// Receives each log record that passes the configured levels, with its
+/// level, target and message
+pub type LogSink = Box<Fn(Level, &str, &str) + Send + Sync>;
+
+/// The process wide logger.  The log crate only allows one logger to be set,
+/// so its configuration and sink can be replaced each time an application
+/// context is created
+#[derive(Default)]
+pub struct NativeLogger {
+  target: RwLock<Option<(LogConfig, LogSink)>>
+}
+
+impl NativeLogger {
+
+  pub fn set_target(&self, config: LogConfig, sink: LogSink) {
+    if let Ok(mut target) = self.target.write() {
+      *target = Some((config, sink));
+    }
+  }
+}
+
+impl Log for NativeLogger {
+  fn enabled(&self, metadata: &Metadata) -> bool {
+    match self.target.read() {
+      Ok(target) => match *target {
+        Some((ref config, _)) =>
+            metadata.level() <= config.level_for(metadata.target()),
+        None => false
+      },
+      Err(_) => false
+    }
+  }
+
+  fn log(&self, record: &Record) {
+    if let Ok(target) = self.target.read() {
+      if let Some((ref config, ref sink)) = *target {
+        if record.level() <= config.level_for(record.target()) {
+          sink(record.level(),
+              record.target(),
+              &format!("{}", record.args()));
+        }
+      }
+    }
+  }
+
+  fn flush(&self) {}
+}
+
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+use log::LevelFilter;
+
+#[test]
+fn test_forwards_enabled_records() {
+  let logger = NativeLogger::default();
+  let logged = Arc::new(Mutex::new(Vec::new()));
+  let logged_copy = logged.clone();
+
+  logger.set_target(
+      LogConfig::new(LevelFilter::Warn)
+          .with_module_level("fourfours::event", LevelFilter::Debug),
+      Box::new(move |level, target, message| {
+        logged_copy.lock().unwrap()
+            .push(format!("{} {} {}", level, target, message));
+      }));
+
+  for &(level, target) in &[
+      (Level::Debug, "fourfours::event::event_bus"),
+      (Level::Debug, "fourfours::math"),
+      (Level::Error, "fourfours::math")] {
+    logger.log(&Record::builder()
+        .level(level)
+        .target(target)
+        .args(format_args!("hello"))
+        .build());
+  }
+
+  assert_eq!(*logged.lock().unwrap(), vec![
+      "DEBUG fourfours::event::event_bus hello",
+      "ERROR fourfours::math hello"]);
+}
@@ -5,17 +5,62 @@ use math::{
 };
 
 use math::syntax::{
+  failure_offset,
   parse,
   Expr
 };
 
 use math::Number;
 
+use model::{
+  Card,
+  GameSetup,
+  RuleViolation
+};
+
 pub struct MathEngine {
 }
 
 impl MathEngine {
 
+  /// Evaluate the given cards as an expression after checking them against
+  /// the rules of the given game setup
+  pub fn evaluate_cards(&self, cards: &Vec<Card>, setup: &GameSetup)
+      -> MathResult {
+    setup.rules().validate(cards, setup.required_cards())
+        .map_err(|violation| violation.to_string())?;
+
+    let input : String = cards.iter().map(Card::to_token).collect();
+
+    self.evaluate(&input)
+  }
+
+  /// Find the index of the card where the given cards stop forming a valid
+  /// expression, if there is one.  Cards joined against the rules count as
+  /// invalid, but other rule violations don't point at a single card
+  pub fn find_error_card(&self, cards: &Vec<Card>, setup: &GameSetup)
+      -> Option<usize> {
+    if let Err(RuleViolation::Concatenation(ord))
+        = setup.rules().validate(cards, setup.required_cards()) {
+      return Some(ord)
+    }
+
+    let tokens : Vec<String> = cards.iter().map(Card::to_token).collect();
+    let offset = failure_offset(&tokens.concat())?;
+
+    let mut end = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+      end += token.len();
+
+      if offset < end {
+        return Some(i)
+      }
+    }
+
+    if cards.is_empty() { None } else { Some(cards.len() - 1) }
+  }
+
   pub fn evaluate(&self, input: &str) -> MathResult {
 
     match parse(input) {
@@ -125,3 +170,17 @@ impl MathEngine {
 
 }
 
+#[test]
+fn test_find_error_card() {
+  let engine = MathEngine{};
+  let setup = GameSetup::classic();
+  let four = required_number_card!(4);
+
+  let valid = vec![four.clone(), Card::Plus, four.clone()];
+  let dangling = vec![four.clone(), Card::Plus];
+  let unmatched = vec![four.clone(), Card::ParenR, Card::Plus, four.clone()];
+
+  assert_eq!(engine.find_error_card(&valid, &setup), None);
+  assert_eq!(engine.find_error_card(&dangling, &setup), Some(1));
+  assert_eq!(engine.find_error_card(&unmatched, &setup), Some(1));
+}
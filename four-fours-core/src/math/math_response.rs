@@ -1,5 +1,6 @@
 
 
+#[derive(Clone)]
 pub struct MathResponse {
   pub value: f64,
   pub tex: String,
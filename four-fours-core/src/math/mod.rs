@@ -8,6 +8,7 @@ pub use self::eval_func::EvalFunc;
 pub use self::eval_prod_term::EvalProdTerm;
 pub use self::eval_prod::EvalProd;
 pub use self::eval_sum::EvalSum;
+pub use self::tex_layout::{TexLayout, TexElement};
 
 pub use self::traits::*;
 
@@ -24,3 +25,4 @@ mod eval_prod;
 mod eval_prod_term;
 mod eval_exp;
 mod eval_func;
+mod tex_layout;
@@ -355,6 +355,26 @@ pub fn parse(input: &str) -> Result<(Expr,Number),String> {
   }
 }
 
+/// Get the offset in bytes into the given input where parsing fails, or
+/// none if the whole input parses
+pub fn failure_offset(input: &str) -> Option<usize> {
+  match expr(CompleteStr(input)) {
+    Ok((remainder, _)) => {
+      if remainder.len() == 0 {
+        None
+      }
+      else {
+        Some(input.len() - remainder.len())
+      }
+    },
+    Err(nom::Err::Error(nom::Context::Code(remainder, _)))
+        | Err(nom::Err::Failure(nom::Context::Code(remainder, _))) => {
+      Some(input.len() - remainder.len())
+    },
+    Err(_) => Some(0)
+  }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -726,6 +746,14 @@ mod tests {
             BigUint::from_str("620448401733239439360000").unwrap()));
     }
 
+    #[test]
+    fn test_failure_offset() {
+        assert_eq!(failure_offset("4+4"), None);
+        assert_eq!(failure_offset("4+4)"), Some(3));
+        assert_eq!(failure_offset("4*+4"), Some(1));
+        assert_eq!(failure_offset(")"), Some(0));
+    }
+
     #[test]
     fn test_square_of_root() {
         let parsed = parse(" ( √ 2 ) ^ 2").unwrap();
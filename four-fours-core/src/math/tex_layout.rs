@@ -0,0 +1,172 @@
+
+use rex::{Renderer, RenderSettings, Cursor};
+use rex::fp::F24P8;
+use rex::parser::color::RGBA;
+
+use model::Rect;
+
+const UNITS_PER_EM : f64 = 1000.;
+const DEFAULT_FONT_SIZE : u16 = 48;
+
+/// A single positioned piece of a laid out formula.  Glyphs are placed by the
+/// left edge of their baseline
+#[derive(Debug, Clone, PartialEq)]
+pub enum TexElement {
+  Glyph {
+    codepoint: u32,
+    left: f64,
+    baseline: f64,
+    size: f64
+  },
+  Rule {
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64
+  }
+}
+
+/// A formula laid out by rex into glyphs and rules positioned relative to
+/// the top left of the formula
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TexLayout {
+  pub width: f64,
+  pub height: f64,
+  pub elements: Vec<TexElement>
+}
+
+impl TexLayout {
+
+  /// Lay out the given tex using the default font size
+  pub fn new(tex: &str) -> Result<TexLayout, String> {
+    TexRenderer::new(DEFAULT_FONT_SIZE).layout(tex)
+  }
+
+  /// Get a copy of this layout scaled to be as large as possible inside the
+  /// given rectangle and centered within it
+  pub fn fit_to(&self, rect: &Rect) -> TexLayout {
+    if self.width <= 0. || self.height <= 0. {
+      return TexLayout::default()
+    }
+
+    let scale = (rect.size.width / self.width)
+        .min(rect.size.height / self.height);
+    let width = self.width * scale;
+    let height = self.height * scale;
+    let left = rect.top_left.x + (rect.size.width - width) / 2.;
+    let top = rect.top_left.y + (rect.size.height - height) / 2.;
+
+    TexLayout {
+      width: width,
+      height: height,
+      elements: self.elements.iter()
+          .map(|element| {
+            match *element {
+              TexElement::Glyph { codepoint, left: l, baseline, size } => {
+                TexElement::Glyph {
+                  codepoint: codepoint,
+                  left: left + l * scale,
+                  baseline: top + baseline * scale,
+                  size: size * scale
+                }
+              },
+              TexElement::Rule { left: l, top: t, width: w, height: h } => {
+                TexElement::Rule {
+                  left: left + l * scale,
+                  top: top + t * scale,
+                  width: w * scale,
+                  height: h * scale
+                }
+              }
+            }
+          })
+          .collect()
+    }
+  }
+}
+
+/// Rex renderer that collects glyph and rule positions instead of drawing
+struct TexRenderer {
+  settings: RenderSettings
+}
+
+impl TexRenderer {
+
+  fn new(font_size: u16) -> TexRenderer {
+    TexRenderer {
+      settings: RenderSettings::default()
+          .font_size(font_size)
+          .horz_padding(F24P8::from(0))
+          .vert_padding(F24P8::from(0))
+    }
+  }
+
+  fn layout(&self, tex: &str) -> Result<TexLayout, String> {
+    self.render(tex).map_err(|e| format!("Failed to lay out tex: {:?}", e))
+  }
+
+  fn to_points(&self, value: F24P8) -> f64 {
+    f64::from(value) / UNITS_PER_EM * self.settings.font_size as f64
+  }
+}
+
+impl Renderer for TexRenderer {
+  type Out = TexLayout;
+
+  fn settings(&self) -> &RenderSettings {
+    &self.settings
+  }
+
+  fn prepare(&self, out: &mut TexLayout, width: F24P8, height: F24P8) {
+    out.width = self.to_points(width);
+    out.height = self.to_points(height);
+  }
+
+  fn symbol(&self, out: &mut TexLayout, pos: Cursor, symbol: u32, scale: f64) {
+    out.elements.push(TexElement::Glyph {
+      codepoint: symbol,
+      left: self.to_points(pos.x),
+      baseline: self.to_points(pos.y),
+      size: self.settings.font_size as f64 * scale
+    });
+  }
+
+  fn rule(&self,
+      out: &mut TexLayout,
+      pos: Cursor,
+      width: F24P8,
+      height: F24P8) {
+    out.elements.push(TexElement::Rule {
+      left: self.to_points(pos.x),
+      top: self.to_points(pos.y),
+      width: self.to_points(width),
+      height: self.to_points(height)
+    });
+  }
+
+  fn color<F>(&self, out: &mut TexLayout, _: RGBA, mut contents: F)
+      where F: FnMut(&Self, &mut TexLayout) {
+    contents(self, out)
+  }
+}
+
+#[test]
+fn test_fit_to() {
+  let layout = TexLayout {
+    width: 20.,
+    height: 10.,
+    elements: vec![
+      TexElement::Glyph { codepoint: 52, left: 0., baseline: 8., size: 10. },
+      TexElement::Rule { left: 10., top: 4., width: 10., height: 1. }
+    ]
+  };
+
+  let fitted = layout.fit_to(&Rect::new(100., 50., 40., 40.));
+
+  assert_eq!(fitted.width, 40.);
+  assert_eq!(fitted.height, 20.);
+  assert_eq!(fitted.elements, vec![
+    TexElement::Glyph { codepoint: 52, left: 100., baseline: 76., size: 20. },
+    TexElement::Rule { left: 120., top: 68., width: 20., height: 2. }
+  ]);
+}
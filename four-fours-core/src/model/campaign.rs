@@ -0,0 +1,217 @@
+
+use std::collections::BTreeMap;
+
+use model::{
+  Card,
+  GameSetup,
+  GameState
+};
+
+const INTEGER_TOLERANCE : f64 = 1e-9;
+
+pub const DEFAULT_MIN_GOAL : i64 = 0;
+pub const DEFAULT_MAX_GOAL : i64 = 100;
+
+/// What crediting a value did to a campaign
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Credit {
+  /// The value missed every goal, or didn't improve on the goal's solution
+  Unchanged,
+  /// The goal was reached for the first time
+  Reached(i64),
+  /// The goal was already reached, and the new solution is shorter
+  Improved(i64)
+}
+
+/// Progress toward making every integer in a range out of the same set of
+/// digits.  The first solution found for each goal is kept (or a shorter
+/// one if found later) so it can be used as a starting point for others
+#[derive(Debug, Clone, PartialEq)]
+pub struct Campaign {
+  digits: Vec<i64>,
+  min_goal: i64,
+  max_goal: i64,
+  solutions: BTreeMap<i64, Vec<Card>>
+}
+
+impl Campaign {
+
+  pub fn new(digits: Vec<i64>, min_goal: i64, max_goal: i64) -> Campaign {
+    Campaign {
+      digits: digits,
+      min_goal: min_goal,
+      max_goal: max_goal,
+      solutions: BTreeMap::new()
+    }
+  }
+
+  /// Create a campaign for the digits required by the given setup over the
+  /// default range of goals
+  pub fn for_setup(setup: &GameSetup) -> Campaign {
+    Campaign::new(digits_of(setup), DEFAULT_MIN_GOAL, DEFAULT_MAX_GOAL)
+  }
+
+  pub fn digits(&self) -> &Vec<i64> {
+    &self.digits
+  }
+
+  pub fn min_goal(&self) -> i64 {
+    self.min_goal
+  }
+
+  pub fn max_goal(&self) -> i64 {
+    self.max_goal
+  }
+
+  /// True if this campaign is played with the digits of the given setup
+  pub fn matches(&self, setup: &GameSetup) -> bool {
+    self.digits == digits_of(setup)
+  }
+
+  pub fn is_reached(&self, goal: i64) -> bool {
+    self.solutions.contains_key(&goal)
+  }
+
+  pub fn reached_count(&self) -> usize {
+    self.solutions.len()
+  }
+
+  pub fn goal_count(&self) -> usize {
+    (self.max_goal - self.min_goal + 1) as usize
+  }
+
+  pub fn is_complete(&self) -> bool {
+    self.reached_count() == self.goal_count()
+  }
+
+  pub fn solutions(&self) -> &BTreeMap<i64, Vec<Card>> {
+    &self.solutions
+  }
+
+  pub fn solution_for(&self, goal: i64) -> Option<&Vec<Card>> {
+    self.solutions.get(&goal)
+  }
+
+  /// Get the lowest goal in the range that has not been reached yet
+  pub fn next_unreached_goal(&self) -> Option<i64> {
+    (self.min_goal..(self.max_goal + 1))
+        .find(|goal| !self.is_reached(*goal))
+  }
+
+  pub fn setup_for(&self, goal: i64) -> GameSetup {
+    GameSetup::simple_new(goal, self.digits.clone())
+  }
+
+  /// Create the game state for the given goal.  If any goal has been reached
+  /// already, the solution of the closest one is put in play to start from
+  pub fn game_state_for(&self, goal: i64) -> GameState {
+    let setup = self.setup_for(goal);
+
+    let closest_solution = self.solutions.iter()
+        .min_by_key(|&(reached_goal, _)| (reached_goal - goal).abs())
+        .map(|(_, cards)| cards.clone());
+
+    match closest_solution {
+      Some(cards) => GameState::new_with_cards_in_play(setup, cards),
+      None => GameState::new(setup)
+    }
+  }
+
+  /// Credit the goal (if any) that the given value hits, keeping the given
+  /// cards as its solution if it's the first or shortest one
+  pub fn credit(&mut self, value: f64, cards: &Vec<Card>) -> Credit {
+    let rounded = value.round();
+
+    if !value.is_finite() || (value - rounded).abs() > INTEGER_TOLERANCE {
+      return Credit::Unchanged
+    }
+
+    let goal = rounded as i64;
+
+    if goal < self.min_goal || goal > self.max_goal {
+      return Credit::Unchanged
+    }
+
+    let result = match self.solutions.get(&goal) {
+      Some(existing) if cards.len() < existing.len() => Credit::Improved(goal),
+      Some(_) => return Credit::Unchanged,
+      None => Credit::Reached(goal)
+    };
+
+    self.solutions.insert(goal, cards.clone());
+    result
+  }
+
+  /// Record a solution for the given goal without evaluating it.  Used when
+  /// restoring saved progress
+  pub fn restore_solution(&mut self, goal: i64, cards: Vec<Card>) {
+    self.solutions.insert(goal, cards);
+  }
+}
+
+fn digits_of(setup: &GameSetup) -> Vec<i64> {
+  setup.required_cards().iter()
+      .filter_map(|card| {
+        match card {
+          Card::Number(num, _) => Some(*num),
+          _ => None
+        }
+      })
+      .collect()
+}
+
+#[test]
+fn test_credit_any_goal_in_range() {
+  let mut campaign = Campaign::new(vec![4, 4, 4, 4], 0, 10);
+  let cards = vec![
+    required_number_card!(4),
+    Card::Divide,
+    required_number_card!(4),
+    Card::Plus,
+    required_number_card!(4),
+    Card::Minus,
+    required_number_card!(4)
+  ];
+
+  assert_eq!(campaign.credit(1.0, &cards), Credit::Reached(1));
+  assert_eq!(campaign.credit(1.0, &cards), Credit::Unchanged);
+  assert_eq!(campaign.credit(7.5, &cards), Credit::Unchanged);
+  assert_eq!(campaign.credit(11.0, &cards), Credit::Unchanged);
+  assert_eq!(campaign.credit(8.0 - 1e-12, &cards), Credit::Reached(8));
+
+  assert_eq!(campaign.reached_count(), 2);
+  assert_eq!(campaign.next_unreached_goal(), Some(0));
+
+  let shorter = vec![
+    required_number_card!(4),
+    Card::Divide,
+    required_number_card!(4)
+  ];
+
+  assert_eq!(campaign.credit(1.0, &shorter), Credit::Improved(1));
+  assert_eq!(campaign.credit(1.0, &cards), Credit::Unchanged);
+  assert_eq!(campaign.solution_for(1), Some(&shorter));
+}
+
+#[test]
+fn test_reuse_closest_solution() {
+  let mut campaign = Campaign::new(vec![4, 4, 4, 4], 0, 10);
+  let cards = vec![
+    required_number_card!(4),
+    Card::Plus,
+    required_number_card!(4),
+    Card::Minus,
+    required_number_card!(4),
+    Card::Minus,
+    required_number_card!(4)
+  ];
+
+  assert_eq!(GameState::new(campaign.setup_for(2)).cards_in_play().len(), 4);
+
+  campaign.credit(0.0, &cards);
+
+  let state = campaign.game_state_for(1);
+
+  assert_eq!(state.setup().goal(), 1);
+  assert_eq!(state.cards_in_play(), &cards);
+}
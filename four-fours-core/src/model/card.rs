@@ -8,7 +8,7 @@ macro_rules! required_number_card {
 macro_rules! define_cards {
   ($($op:ident),*) => {
 
-    #[derive(Clone, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum Card {
       Number(i64, bool),
       $(
@@ -60,4 +60,84 @@ impl Card {
       _ => false
     }
   }
-}
\ No newline at end of file
+
+  /// Get the text this card contributes to an expression string
+  pub fn to_token(&self) -> String {
+    match self {
+      Card::Number(num, _) => num.to_string(),
+      Card::Plus => String::from("+"),
+      Card::Minus => String::from("-"),
+      Card::Times => String::from("*"),
+      Card::Divide => String::from("/"),
+      Card::ParenL => String::from("("),
+      Card::ParenR => String::from(")"),
+      Card::Power => String::from("^"),
+      Card::Radical => String::from("√"),
+      Card::Inverse => String::from("^-1"),
+      Card::Factorial => String::from("!"),
+      Card::Decimal => String::from(".")
+    }
+  }
+
+  /// Get the card typed with the given key, if any.  Digits produce required
+  /// number cards
+  pub fn from_key(key: char) -> Option<Card> {
+    match key {
+      '0'..='9' => key.to_digit(10).map(|d| required_number_card!(d as i64)),
+      'x' | 'X' => Some(Card::Times),
+      'r' | 'R' | 's' | 'S' => Some(Card::Radical),
+      'i' | 'I' => Some(Card::Inverse),
+      _ => Card::from_token(&key.to_string())
+    }
+  }
+
+  /// Get the card for the given token (the inverse of `to_token`).  Number
+  /// tokens produce required number cards
+  pub fn from_token(token: &str) -> Option<Card> {
+    match token {
+      "+" => Some(Card::Plus),
+      "-" => Some(Card::Minus),
+      "*" => Some(Card::Times),
+      "/" => Some(Card::Divide),
+      "(" => Some(Card::ParenL),
+      ")" => Some(Card::ParenR),
+      "^" => Some(Card::Power),
+      "√" => Some(Card::Radical),
+      "^-1" => Some(Card::Inverse),
+      "!" => Some(Card::Factorial),
+      "." => Some(Card::Decimal),
+      _ => {
+        token.parse::<i64>().ok()
+            .and_then(|num| {
+              if num >= 0 && num <= 9 {
+                Some(required_number_card!(num))
+              }
+              else {
+                None
+              }
+            })
+      }
+    }
+  }
+}
+
+#[test]
+fn test_token_round_trip() {
+  let mut cards = all_non_number_cards!();
+  cards.push(required_number_card!(7));
+
+  for card in cards {
+    assert_eq!(Card::from_token(&card.to_token()), Some(card));
+  }
+
+  assert_eq!(Card::from_token("12"), None);
+  assert_eq!(Card::from_token("x"), None);
+}
+#[test]
+fn test_from_key() {
+  assert_eq!(Card::from_key('4'), Some(required_number_card!(4)));
+  assert_eq!(Card::from_key('+'), Some(Card::Plus));
+  assert_eq!(Card::from_key('x'), Some(Card::Times));
+  assert_eq!(Card::from_key('r'), Some(Card::Radical));
+  assert_eq!(Card::from_key('q'), None);
+}
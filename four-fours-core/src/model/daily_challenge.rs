@@ -0,0 +1,374 @@
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use model::{
+  Card,
+  GameSetup,
+  SetupOrigin
+};
+
+use util::SeededRandom;
+
+const SECONDS_PER_DAY : u64 = 86_400;
+
+const MIN_GOAL : i64 = 0;
+const MAX_GOAL : i64 = 50;
+const REQUIRED_CARD_COUNT : usize = 4;
+const OPTIONAL_CARD_PROBABILITY : f64 = 0.5;
+
+/// Calendar date (UTC) that identifies a single daily challenge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChallengeDate {
+  pub year: i64,
+  pub month: u32,
+  pub day: u32
+}
+
+impl ChallengeDate {
+
+  /// Create the date with the given year, month (1-12) and day of the
+  /// month, or an error if there is no such date
+  pub fn new(year: i64, month: u32, day: u32)
+      -> Result<ChallengeDate, String> {
+    if month < 1 || month > 12 || day < 1 || day > days_in_month(year, month) {
+      return Err(format!("Invalid challenge date {}-{}-{}", year, month, day))
+    }
+
+    Ok(ChallengeDate { year: year, month: month, day: day })
+  }
+
+  /// Get the current date in UTC according to the system clock
+  pub fn today() -> ChallengeDate {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    ChallengeDate::from_days_since_epoch((secs / SECONDS_PER_DAY) as i64)
+  }
+
+  /// Convert a count of days since 1970-01-01 into a calendar date
+  pub fn from_days_since_epoch(days: i64) -> ChallengeDate {
+    let z = days + 719_468;
+    let era = (if z >= 0 { z } else { z - 146_096 }) / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    ChallengeDate { year: year, month: month as u32, day: day as u32 }
+  }
+
+  /// Get the number of days between 1970-01-01 and this date
+  pub fn days_since_epoch(&self) -> i64 {
+    let month = self.month as i64;
+    let year = self.year - if month <= 2 { 1 } else { 0 };
+    let era = (if year >= 0 { year } else { year - 399 }) / 400;
+    let yoe = year - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+        + self.day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+  }
+
+  /// Seed used to generate the challenge for this date.  This must never
+  /// change or players on different versions will get different puzzles
+  pub fn seed(&self) -> u64 {
+    (self.year as u64) * 10_000 + (self.month as u64) * 100 + self.day as u64
+  }
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+  match month {
+    2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    _ => 31
+  }
+}
+
+/// A fraction in lowest terms with a positive denominator, so equal values
+/// have equal representations
+type Rational = (i64, i64);
+
+fn gcd(a: i64, b: i64) -> i64 {
+  if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+fn rational(numerator: i64, denominator: i64) -> Rational {
+  let divisor = gcd(numerator, denominator) * denominator.signum();
+
+  (numerator / divisor, denominator / divisor)
+}
+
+/// Get each way of combining the two given values with the operators every
+/// daily challenge has
+fn combine(left: Rational, right: Rational) -> Vec<(Card, Rational)> {
+  let (ln, ld) = left;
+  let (rn, rd) = right;
+
+  let mut result = vec![
+    (Card::Plus, rational(ln * rd + rn * ld, ld * rd)),
+    (Card::Minus, rational(ln * rd - rn * ld, ld * rd)),
+    (Card::Times, rational(ln * rn, ld * rd))
+  ];
+
+  if rn != 0 {
+    result.push((Card::Divide, rational(ln * rd, ld * rn)));
+  }
+
+  result
+}
+
+/// Find every whole number goal from `MIN_GOAL` to `MAX_GOAL` that can be
+/// made from the required copies of the given digit using only the cards
+/// every daily challenge has, along with cards that make each one
+fn solvable_goals(digit: i64) -> BTreeMap<i64, Vec<Card>> {
+  // Values that can be made with each count of digits
+  let mut reachable : Vec<BTreeMap<Rational, Vec<Card>>>
+      = vec![BTreeMap::new()];
+
+  for count in 1..(REQUIRED_CARD_COUNT + 1) {
+    let mut values = BTreeMap::new();
+    let joined = (0..count).fold(0, |total, _| total * 10 + digit);
+
+    values.insert((joined, 1), vec![required_number_card!(digit); count]);
+
+    for left_count in 1..count {
+      for (left, left_cards) in &reachable[left_count] {
+        for (right, right_cards) in &reachable[count - left_count] {
+          for (operator, value) in combine(*left, *right) {
+            values.entry(value).or_insert_with(|| {
+              let mut cards = vec![Card::ParenL];
+              cards.extend(left_cards.iter().cloned());
+              cards.push(operator);
+              cards.extend(right_cards.iter().cloned());
+              cards.push(Card::ParenR);
+              cards
+            });
+          }
+        }
+      }
+    }
+
+    reachable.push(values);
+  }
+
+  reachable[REQUIRED_CARD_COUNT].iter()
+      .filter(|&(&(numerator, denominator), _)| {
+        denominator == 1 && numerator >= MIN_GOAL && numerator <= MAX_GOAL
+      })
+      .map(|(&(numerator, _), cards)| (numerator, cards.clone()))
+      .collect()
+}
+
+/// The puzzle for a single day.  Every device generates the same setup for
+/// the same date
+#[derive(Getters)]
+pub struct DailyChallenge {
+  #[get = "pub"] date: ChallengeDate,
+  #[get = "pub"] setup: GameSetup
+}
+
+impl DailyChallenge {
+
+  pub fn today() -> DailyChallenge {
+    DailyChallenge::for_date(&ChallengeDate::today())
+  }
+
+  pub fn for_date(date: &ChallengeDate) -> DailyChallenge {
+    let mut random = SeededRandom::new(date.seed());
+
+    let digit = random.next_in_range(1, 10);
+
+    // Only goals that can be made without the optional cards are picked, so
+    // every challenge can be solved
+    let goals : Vec<i64> = solvable_goals(digit).keys().cloned().collect();
+    let goal = goals[random.next_in_range(0, goals.len() as i64) as usize];
+
+    let required_cards = (0..REQUIRED_CARD_COUNT)
+        .map(|_| required_number_card!(digit))
+        .collect();
+
+    let supply_cards = all_non_number_cards!().into_iter()
+        .filter(|card| {
+          match card {
+            Card::Plus
+                | Card::Minus
+                | Card::Times
+                | Card::Divide
+                | Card::ParenL
+                | Card::ParenR => true,
+            _ => random.next_bool(OPTIONAL_CARD_PROBABILITY)
+          }
+        })
+        .collect();
+
+    DailyChallenge {
+      date: *date,
+      setup: GameSetup::new(goal, required_cards, supply_cards)
+          .with_origin(SetupOrigin::DailyChallenge)
+    }
+  }
+}
+
+/// A player's outcome for one daily challenge
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyResult {
+  pub date: ChallengeDate,
+  pub solved: bool,
+  pub cards_used: usize,
+  pub elapsed_millis: u64
+}
+
+impl DailyResult {
+
+  /// Compare two results for the same challenge.  `Greater` means this
+  /// result is better: solving beats not solving, then fewer cards, then
+  /// a faster time
+  pub fn compare(&self, other: &DailyResult) -> Ordering {
+    self.solved.cmp(&other.solved)
+        .then_with(|| other.cards_used.cmp(&self.cards_used))
+        .then_with(|| other.elapsed_millis.cmp(&self.elapsed_millis))
+  }
+
+  pub fn is_better_than(&self, other: &DailyResult) -> bool {
+    self.compare(other) == Ordering::Greater
+  }
+}
+
+/// The best result a player has recorded for each daily challenge
+#[derive(Default)]
+pub struct DailyResults {
+  best: HashMap<ChallengeDate, DailyResult>
+}
+
+impl DailyResults {
+
+  /// Record the given result.  Returns true if it is now the best result
+  /// for its date
+  pub fn record(&mut self, result: DailyResult) -> bool {
+    let is_best = match self.best.get(&result.date) {
+      Some(existing) => result.is_better_than(existing),
+      None => true
+    };
+
+    if is_best {
+      self.best.insert(result.date, result);
+    }
+
+    is_best
+  }
+
+  pub fn best_for(&self, date: &ChallengeDate) -> Option<&DailyResult> {
+    self.best.get(date)
+  }
+}
+
+#[test]
+fn test_date_round_trip() {
+  let date = ChallengeDate::new(2019, 2, 14).unwrap();
+
+  assert_eq!(date.days_since_epoch(), 17_941);
+  assert_eq!(ChallengeDate::from_days_since_epoch(17_941), date);
+  assert_eq!(ChallengeDate::from_days_since_epoch(0),
+      ChallengeDate::new(1970, 1, 1).unwrap());
+  assert_eq!(ChallengeDate::from_days_since_epoch(11_016),
+      ChallengeDate::new(2000, 2, 29).unwrap());
+}
+
+#[test]
+fn test_invalid_dates() {
+  assert!(ChallengeDate::new(2019, 0, 1).is_err());
+  assert!(ChallengeDate::new(2019, 13, 1).is_err());
+  assert!(ChallengeDate::new(2019, 4, 31).is_err());
+  assert!(ChallengeDate::new(2019, 2, 29).is_err());
+  assert!(ChallengeDate::new(1900, 2, 29).is_err());
+  assert!(ChallengeDate::new(2000, 2, 29).is_ok());
+}
+
+#[test]
+fn test_challenge_never_changes() {
+  let challenge = DailyChallenge::for_date(
+      &ChallengeDate::new(2019, 2, 14).unwrap());
+
+  assert_eq!(challenge.setup().goal(), 9);
+  assert_eq!(challenge.setup().origin(), SetupOrigin::DailyChallenge);
+  assert_eq!(*challenge.setup().required_cards(),
+      vec![required_number_card!(4); REQUIRED_CARD_COUNT]);
+  assert_eq!(*challenge.setup().supply_cards(), vec![
+      Card::Plus,
+      Card::Minus,
+      Card::Times,
+      Card::Divide,
+      Card::ParenL,
+      Card::ParenR,
+      Card::Radical,
+      Card::Inverse]);
+}
+
+#[cfg(test)]
+use math::MathEngine;
+
+#[test]
+fn test_challenges_are_solvable() {
+  let engine = MathEngine{};
+  let first_day = ChallengeDate::new(2019, 1, 1).unwrap().days_since_epoch();
+
+  for days in first_day..(first_day + 366) {
+    let challenge = DailyChallenge::for_date(
+        &ChallengeDate::from_days_since_epoch(days));
+    let setup = challenge.setup();
+
+    let digit = match setup.required_cards()[0] {
+      Card::Number(digit, _) => digit,
+      ref card => panic!("Required card {:?} is not a number", card)
+    };
+
+    let solution = solvable_goals(digit).remove(&setup.goal())
+        .expect("Goal has no solution");
+    let value = engine.evaluate_cards(&solution, setup)
+        .expect("Solution can't be evaluated")
+        .value;
+
+    assert!(setup.is_solved_by(value),
+        "{:?} makes {} rather than {}", solution, value, setup.goal());
+  }
+}
+
+#[test]
+fn test_same_date_same_challenge() {
+  let date = ChallengeDate::new(2019, 2, 14).unwrap();
+
+  let first = DailyChallenge::for_date(&date);
+  let second = DailyChallenge::for_date(&date);
+
+  assert_eq!(first.setup().goal(), second.setup().goal());
+  assert_eq!(first.setup().required_cards(), second.setup().required_cards());
+  assert_eq!(first.setup().supply_cards(), second.setup().supply_cards());
+  assert_eq!(first.setup().required_cards().len(), REQUIRED_CARD_COUNT);
+}
+
+#[test]
+fn test_record_keeps_best() {
+  let date = ChallengeDate::new(2019, 2, 14).unwrap();
+  let mut results = DailyResults::default();
+
+  let unsolved = DailyResult {
+    date: date, solved: false, cards_used: 4, elapsed_millis: 1000 };
+  let solved = DailyResult {
+    date: date, solved: true, cards_used: 7, elapsed_millis: 9000 };
+  let shorter = DailyResult {
+    date: date, solved: true, cards_used: 5, elapsed_millis: 20000 };
+
+  assert!(results.record(unsolved));
+  assert!(results.record(solved.clone()));
+  assert!(results.record(shorter.clone()));
+  assert!(!results.record(solved));
+  assert_eq!(results.best_for(&date), Some(&shorter));
+}
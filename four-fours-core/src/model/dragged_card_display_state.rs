@@ -12,7 +12,7 @@ use ui::{
 #[derive(Getters,MutGetters,Setters)]
 pub struct DraggedCardDisplayState<S : Sprite> {
 
-  #[get = "pub"]
+  #[get = "pub"] #[get_mut = "pub"]
   card: UiCard<S>,
 
   #[get = "pub"] orig_play_area_ord: Option<usize>,
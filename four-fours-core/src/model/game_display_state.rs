@@ -9,7 +9,8 @@ use model::{
   Point,
   Rect,
   DraggedCardDisplayState,
-  CardFindResponse
+  CardFindResponse,
+  PlayAreaLayout
 };
 
 use ui::{
@@ -35,6 +36,10 @@ pub struct GameDisplayState<S> where S: Sprite {
   #[get = "pub"] #[set = "pub"] #[get_mut = "pub"]
   card_in_flight: Option<DraggedCardDisplayState<S>>,
 
+  /// Position in the cards in play where typed cards are inserted.  None
+  /// means after the last card
+  typing_cursor: Option<usize>,
+
   /// List of points that represent the places that cards can be added
   /// to the playing area
   #[get = "pub"] #[set = "pub"] #[get_mut = "pub"]
@@ -55,6 +60,9 @@ pub struct GameDisplayState<S> where S: Sprite {
   #[get = "pub"] #[set = "pub"] play_area_row_count: f64,
   #[get = "pub"] #[get_mut = "pub"] play_area_rect: Rect,
 
+  /// Row and column of each play card slot
+  #[get = "pub"] #[set = "pub"] play_area_layout: PlayAreaLayout,
+
   #[get = "pub"] #[set = "pub"] game_area_rect: Rect,
   #[get = "pub"] #[set = "pub"] card_area_rect: Rect,
   #[get = "pub"] #[set = "pub"] tex_area_rect: Rect
@@ -71,6 +79,7 @@ impl <S> Default for GameDisplayState<S> where S: Sprite {
       cards_in_play: Vec::default(),
       supply_cards: Vec::default(),
       card_in_flight: Option::default(),
+      typing_cursor: None,
 
       bind_points: Vec::default(),
 
@@ -89,6 +98,7 @@ impl <S> Default for GameDisplayState<S> where S: Sprite {
 
       play_area_row_count: 0.,
       play_area_rect: Rect::default(),
+      play_area_layout: PlayAreaLayout::default(),
 
       game_area_rect: Rect::default(),
       card_area_rect: Rect::default(),
@@ -110,12 +120,6 @@ impl <S,T> GameDisplayState<S>
       current_bind_point: &Option<usize>)
           -> Option<usize> {
 
-    let mut min_row_dist : f64 = 1_000_000.;
-    let mut best_row : Option<&Vec<(f64,usize)>> = None;
-
-    let x = &point.x;
-    let y = &point.y;
-
     let threshold_dist = if current_bind_point.is_some() {
         self.play_card_size.height
             * MAX_DIST_FROM_PLAY_AREA_FRAC_OF_CARD_HEIGHT
@@ -124,38 +128,25 @@ impl <S,T> GameDisplayState<S>
         0.
       };
 
-
     if self.play_area_rect.distance_to(point) > threshold_dist {
       return None
     }
 
+    let mut min_dist : f64 = 1_000_000.;
+    let mut best_point : Option<usize> = None;
+
     for (row, columns) in &self.bind_points {
-      let row_dist = (row - y).abs();
-      if row_dist < min_row_dist {
-        min_row_dist = row_dist;
-        best_row = Some(columns)
-      }
-    }
+      for (col, bind_point) in columns {
+        let dist = Point::new(*col, *row).distance_to(point);
 
-    match best_row {
-      None => {
-        None
-      },
-      Some(columns) => {
-        let mut min_col_dist : f64 = 1_000_000.;
-        let mut best_point : Option<usize> = None;
-        for (col, bind_point) in columns {
-          let col_dist = (col - x).abs();
-
-          if col_dist < min_col_dist {
-            min_col_dist = col_dist;
-            best_point = Some(bind_point.clone());
-          }
+        if dist < min_dist {
+          min_dist = dist;
+          best_point = Some(*bind_point);
         }
-
-        best_point
       }
     }
+
+    best_point
   }
 
   /// Get the rectangle that the supply card with the current index should
@@ -196,18 +187,24 @@ impl <S,T> GameDisplayState<S>
     }
   }
 
+  /// Get the rectangle that the play card slot with the given index should
+  /// be displayed within.  Rows shorter than the widest row are centered
   pub fn get_play_card_rect_by_index(&self, i: &usize) -> Option<Rect> {
 
     if *i >= (self.play_card_slots as usize) {
       return None
     }
 
-    let col = (i % (self.play_cards_per_row as usize)) as f64;
-    let row = (i / (self.play_cards_per_row as usize)) as f64;
+    let (row, col) = self.play_area_layout.position_of(*i)?;
+    let row_length = self.play_area_layout.row_length(row) as f64;
+    let indent = (self.play_cards_per_row.min(self.play_card_slots)
+        - row_length).max(0.) / 2.;
+
     let left = self.play_area_rect.top_left.x
-        + (self.play_card_size.width + self.play_card_spacing) * col;
+        + (self.play_card_size.width + self.play_card_spacing)
+            * (col as f64 + indent);
     let top = self.play_area_rect.top_left.y
-        + (self.play_card_size.height + self.play_card_spacing) * row;
+        + (self.play_card_size.height + self.play_card_spacing) * row as f64;
 
     Some(Rect {
       top_left: Point {
@@ -273,48 +270,24 @@ impl <S,T> GameDisplayState<S>
   pub fn get_play_card_at(&self, point: &Point)
       -> Option<CardFindResponse> {
 
-    let card_row = ((point.y - self.play_area_rect.top_left.y)
-        / (self.play_card_size.height + self.play_card_spacing)).floor();
+    let card_index = (0..self.cards_in_play.len())
+        .find(|i| {
+          self.get_play_card_rect_by_index(i)
+              .map(|rect| rect.contains(point))
+              .unwrap_or(false)
+        })?;
 
-    if card_row < 0. || card_row > self.play_area_row_count - 0.5 {
-      return None
-    }
-
-    let card_y = point.y - self.play_area_rect.top_left.y
-        - card_row * (self.play_card_size.height + self.play_card_spacing);
-
-    if card_y > self.play_card_size.height {
-      return None
-    }
-
-    let card_col = ((point.x - self.play_area_rect.top_left.x)
-        / (self.play_card_size.width + self.play_card_spacing)).floor();
-
-    if card_col < 0. {
-      return None
-    }
-
-    let card_index
-        = (card_row * self.play_cards_per_row + card_col).round() as usize;
-
-    if card_index >= self.cards_in_play.len() {
-      return None
-    }
-
-    let card_x = point.x - self.play_area_rect.top_left.x
-        - card_col * (self.play_card_size.width + self.play_card_spacing);
-
-    if card_x > self.play_card_size.width {
-      return None
-    }
+    let card_rect = self.get_play_card_rect_by_index(&card_index)?;
 
     self.cards_in_play.get(card_index)
         .map(|card| {
           CardFindResponse {
             card: card.card().clone(),
             play_area_ord: Some(card_index),
-            card_rect: self.get_play_card_rect_by_index(&card_index).unwrap(),
-            point_in_card: Point::new(card_x, card_y)
+            point_in_card: Point::new(
+                point.x - card_rect.top_left.x,
+                point.y - card_rect.top_left.y),
+            card_rect: card_rect
           }
         })
   }
@@ -329,6 +302,56 @@ impl <S,T> GameDisplayState<S>
 
   }
 
+  /// Get the number of copies of the given card that are in play, including
+  /// the card in flight if it is the same card
+  pub fn count_in_use(&self, card: &Card) -> usize {
+    let in_play = self.cards_in_play.iter()
+        .filter(|ui_card| ui_card.card() == card)
+        .count();
+
+    let in_flight = match self.card_in_flight {
+      Some(ref drag_state) if drag_state.card().card() == card => 1,
+      _ => 0
+    };
+
+    in_play + in_flight
+  }
+
+  /// Get the position in the cards in play where typed cards are inserted
+  pub fn cursor(&self) -> usize {
+    self.typing_cursor
+        .map(|cursor| cursor.min(self.cards_in_play.len()))
+        .unwrap_or_else(|| self.cards_in_play.len())
+  }
+
+  /// Move the typing cursor, keeping it within the cards in play
+  pub fn set_cursor(&mut self, cursor: usize) {
+    self.typing_cursor = if cursor >= self.cards_in_play.len() {
+      None
+    }
+    else {
+      Some(cursor)
+    };
+  }
+
+  /// Get a copy of the cards currently in play in order
+  pub fn cards(&self) -> Vec<Card> {
+    self.cards_in_play.iter().map(|ui_card| ui_card.card().clone()).collect()
+  }
+
+  /// Get the cards that would be in play if the card in flight were dropped
+  /// at its current play area ordinal, or none if it would not be dropped
+  /// into play
+  pub fn preview_cards(&self) -> Option<Vec<Card>> {
+    let drag_state = self.card_in_flight.as_ref()?;
+    let ord = (*drag_state.play_area_ord())?;
+
+    let mut result = self.cards();
+    result.insert(ord, drag_state.card().card().clone());
+
+    Some(result)
+  }
+
   pub fn to_string(&self) -> String {
     cards_to_string(
         &self.cards_in_play.iter().map(UiCard::card).collect())
@@ -339,23 +362,8 @@ impl <S,T> GameDisplayState<S>
 fn cards_to_string(cards: &Vec<&Card>) -> String {
   let mut result = String::with_capacity(cards.len());
 
-  for ref card in cards {
-    match card {
-      Card::Number(num, _) => {
-        result.push((((*num as u8) + ('0' as u8)) as char));
-      },
-      Card::Plus => result.push('+'),
-      Card::Minus => result.push('-'),
-      Card::Times => result.push('*'),
-      Card::Divide => result.push('/'),
-      Card::ParenL => result.push('('),
-      Card::ParenR => result.push(')'),
-      Card::Power => result.push('^'),
-      Card::Radical => result.push('√'),
-      Card::Inverse => result.push_str("^-1"),
-      Card::Factorial => result.push('!'),
-      Card::Decimal => result.push('.')
-    };
+  for card in cards {
+    result.push_str(&card.to_token());
   }
 
   result
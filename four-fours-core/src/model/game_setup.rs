@@ -1,11 +1,30 @@
+use itertools::Itertools;
 
-use model::Card;
+use model::{Card, Rules};
 
-#[derive(Default)]
+const SOLVED_TOLERANCE : f64 = 1e-9;
+
+/// Where a game setup came from.  Only campaign games count toward the
+/// player's campaigns
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetupOrigin {
+  Campaign,
+  DailyChallenge
+}
+
+impl Default for SetupOrigin {
+  fn default() -> SetupOrigin {
+    SetupOrigin::Campaign
+  }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct GameSetup {
   goal: i64,
   required_cards: Vec<Card>,
   supply_cards: Vec<Card>,
+  rules: Rules,
+  origin: SetupOrigin
 }
 
 impl GameSetup {
@@ -16,15 +35,39 @@ impl GameSetup {
         all_non_number_cards!())
   }
 
+  /// The classic puzzle: make the goal using four 4s
+  pub fn classic() -> GameSetup {
+    GameSetup::simple_new(1, vec![4, 4, 4, 4])
+  }
+
   pub fn new(goal: i64, required_cards: Vec<Card>, supply_cards: Vec<Card>)
       -> GameSetup {
+    GameSetup::new_with_rules(
+        goal,
+        required_cards,
+        supply_cards,
+        Rules::default())
+  }
+
+  pub fn new_with_rules(goal: i64,
+      required_cards: Vec<Card>,
+      supply_cards: Vec<Card>,
+      rules: Rules) -> GameSetup {
     GameSetup {
       goal: goal,
       required_cards: required_cards,
-      supply_cards: supply_cards
+      supply_cards: supply_cards,
+      rules: rules,
+      origin: SetupOrigin::default()
     }
   }
 
+  /// Get this setup marked as coming from the given origin
+  pub fn with_origin(mut self, origin: SetupOrigin) -> GameSetup {
+    self.origin = origin;
+    self
+  }
+
   pub fn goal(&self) -> i64 {
     self.goal
   }
@@ -36,4 +79,173 @@ impl GameSetup {
   pub fn supply_cards(&self) -> &Vec<Card> {
     &self.supply_cards
   }
-}
\ No newline at end of file
+
+  /// Get the supply cards that the rules of this game allow to be played
+  pub fn allowed_supply_cards(&self) -> Vec<Card> {
+    self.supply_cards.iter()
+        .filter(|card| self.rules.is_operator_allowed(card))
+        .cloned()
+        .collect()
+  }
+
+  pub fn rules(&self) -> &Rules {
+    &self.rules
+  }
+
+  pub fn origin(&self) -> SetupOrigin {
+    self.origin
+  }
+
+  /// True if the given value is close enough to the goal to count as a
+  /// solution
+  pub fn is_solved_by(&self, value: f64) -> bool {
+    (value - self.goal as f64).abs() < SOLVED_TOLERANCE
+  }
+
+  /// Serialize this setup into a single line of `key=value` fields, the
+  /// format recordings note the setup they were made with in
+  pub fn to_save_string(&self) -> String {
+    let limits = self.rules.supply_limits().iter()
+        .map(|&(ref card, limit)| format!("{}:{}", card.to_token(), limit))
+        .join(",");
+    let max_cards = self.rules.max_card_count().as_ref()
+        .map(|max| max.to_string())
+        .unwrap_or_else(|| String::from(NO_MAX_CARDS));
+    let origin = match self.origin {
+      SetupOrigin::Campaign => CAMPAIGN_ORIGIN,
+      SetupOrigin::DailyChallenge => DAILY_CHALLENGE_ORIGIN
+    };
+
+    format!("goal={} origin={} required={} supply={} require_all={} \
+        concatenation={} max_cards={} operators={} limits={}",
+        self.goal,
+        origin,
+        join_tokens(&self.required_cards),
+        join_tokens(&self.supply_cards),
+        self.rules.require_all_cards(),
+        self.rules.allow_concatenation(),
+        max_cards,
+        join_tokens(self.rules.allowed_operators()),
+        limits)
+  }
+
+  /// Parse a setup written by `to_save_string`.  Fields that are missing
+  /// keep their defaults
+  pub fn from_save_string(saved: &str) -> Result<GameSetup, String> {
+    let mut result = GameSetup::default();
+
+    for field in saved.split_whitespace() {
+      let mut parts = field.splitn(2, '=');
+      let key = parts.next().unwrap_or("");
+      let value = parts.next()
+          .ok_or_else(|| format!("Invalid setup field: {}", field))?;
+
+      match key {
+        "goal" => {
+          result.goal = value.parse::<i64>()
+              .map_err(|_| format!("Invalid goal: {}", field))?;
+        },
+        "origin" => {
+          result.origin = match value {
+            CAMPAIGN_ORIGIN => SetupOrigin::Campaign,
+            DAILY_CHALLENGE_ORIGIN => SetupOrigin::DailyChallenge,
+            _ => return Err(format!("Invalid origin: {}", field))
+          };
+        },
+        "required" => result.required_cards = parse_tokens(value, field)?,
+        "supply" => result.supply_cards = parse_tokens(value, field)?,
+        "require_all" => {
+          result.rules.set_require_all_cards(parse_bool(value, field)?);
+        },
+        "concatenation" => {
+          result.rules.set_allow_concatenation(parse_bool(value, field)?);
+        },
+        "max_cards" => {
+          let max_cards = if value == NO_MAX_CARDS {
+            None
+          }
+          else {
+            Some(value.parse::<usize>()
+                .map_err(|_| format!("Invalid card count: {}", field))?)
+          };
+
+          result.rules.set_max_card_count(max_cards);
+        },
+        "operators" => {
+          result.rules.set_allowed_operators(parse_tokens(value, field)?);
+        },
+        "limits" => {
+          for limit in value.split(',').filter(|l| !l.is_empty()) {
+            let mut limit_parts = limit.rsplitn(2, ':');
+            let count = limit_parts.next()
+                .and_then(|count| count.parse::<usize>().ok())
+                .ok_or_else(|| format!("Invalid limit: {}", field))?;
+            let card = limit_parts.next()
+                .and_then(Card::from_token)
+                .ok_or_else(|| format!("Invalid limit: {}", field))?;
+
+            result.rules.set_supply_limit(card, count);
+          }
+        },
+        _ => return Err(format!("Unrecognized setup field: {}", field))
+      }
+    }
+
+    Ok(result)
+  }
+}
+
+const CAMPAIGN_ORIGIN : &str = "campaign";
+const DAILY_CHALLENGE_ORIGIN : &str = "daily";
+const NO_MAX_CARDS : &str = "none";
+
+fn join_tokens(cards: &Vec<Card>) -> String {
+  cards.iter().map(Card::to_token).join(",")
+}
+
+fn parse_tokens(value: &str, field: &str) -> Result<Vec<Card>, String> {
+  value.split(',')
+      .filter(|token| !token.is_empty())
+      .map(|token| Card::from_token(token)
+          .ok_or_else(|| format!("Invalid card: {}", field)))
+      .collect()
+}
+
+fn parse_bool(value: &str, field: &str) -> Result<bool, String> {
+  value.parse::<bool>().map_err(|_| format!("Invalid flag: {}", field))
+}
+
+#[test]
+fn test_is_solved_by() {
+  let setup = GameSetup::classic();
+
+  assert!(setup.is_solved_by(1.0));
+  assert!(setup.is_solved_by(1.0 + 1e-12));
+  assert!(!setup.is_solved_by(1.5));
+  assert!(!setup.is_solved_by(::std::f64::NAN));
+}
+
+#[test]
+fn test_save_round_trip() {
+  let mut rules = Rules::default();
+  rules.set_require_all_cards(false);
+  rules.set_max_card_count(Some(9));
+  rules.set_allowed_operators(vec![Card::Minus, Card::Inverse]);
+  rules.set_supply_limit(Card::Radical, 2);
+
+  let setup = GameSetup::new_with_rules(
+      17,
+      vec![required_number_card!(2); 3],
+      vec![Card::Minus, Card::Radical, Card::Inverse],
+      rules)
+      .with_origin(SetupOrigin::DailyChallenge);
+
+  let saved = setup.to_save_string();
+
+  assert!(!saved.contains('\n'));
+  assert_eq!(GameSetup::from_save_string(&saved), Ok(setup));
+  assert_eq!(
+      GameSetup::from_save_string(&GameSetup::classic().to_save_string()),
+      Ok(GameSetup::classic()));
+  assert!(GameSetup::from_save_string("goal=many").is_err());
+}
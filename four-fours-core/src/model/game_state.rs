@@ -1,7 +1,7 @@
 
 use model::{GameSetup, Card};
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct GameState {
   setup: GameSetup,
   cards_in_play: Vec<Card>
@@ -15,6 +15,16 @@ impl GameState {
     }
   }
 
+  /// Create a game state that starts with the given cards already in play.
+  /// The cards must include every required card of the setup
+  pub fn new_with_cards_in_play(setup: GameSetup, cards_in_play: Vec<Card>)
+      -> GameState {
+    GameState {
+      setup: setup,
+      cards_in_play: cards_in_play
+    }
+  }
+
   pub fn setup(&self) -> &GameSetup {
     &self.setup
   }
@@ -1,12 +1,23 @@
 pub use self::game_state::GameState;
 pub use self::card::Card;
-pub use self::game_setup::GameSetup;
+pub use self::game_setup::{GameSetup, SetupOrigin};
 pub use self::game_display_state::GameDisplayState;
 pub use self::dragged_card_display_state::DraggedCardDisplayState;
 pub use self::size::Size;
 pub use self::point::Point;
 pub use self::rect::Rect;
 pub use self::card_find_response::CardFindResponse;
+pub use self::rules::{Rules, RuleViolation, MAX_SUPPLY_LIMIT};
+pub use self::campaign::{Campaign, Credit};
+pub use self::player_progress::PlayerProgress;
+pub use self::navigation_stack::{NavigationStack, Screen};
+pub use self::play_area_layout::PlayAreaLayout;
+pub use self::daily_challenge::{
+  ChallengeDate,
+  DailyChallenge,
+  DailyResult,
+  DailyResults
+};
 
 #[macro_use]
 mod card;
@@ -18,4 +29,10 @@ mod dragged_card_display_state;
 mod rect;
 mod point;
 mod size;
-mod card_find_response;
\ No newline at end of file
+mod card_find_response;
+mod daily_challenge;
+mod rules;
+mod campaign;
+mod player_progress;
+mod navigation_stack;
+mod play_area_layout;
\ No newline at end of file
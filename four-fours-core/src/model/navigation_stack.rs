@@ -0,0 +1,82 @@
+
+/// The screens of the application that can be navigated between
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+  Loading,
+  MainMenu,
+  Game,
+  Pause
+}
+
+/// The back-stack of screens the player has navigated through.  The loading
+/// screen is never kept once another screen is shown, so going back can
+/// never return to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavigationStack {
+  screens: Vec<Screen>
+}
+
+impl Default for NavigationStack {
+  fn default() -> NavigationStack {
+    NavigationStack { screens: vec![Screen::Loading] }
+  }
+}
+
+impl NavigationStack {
+
+  /// Record that the given screen is now showing.  If the screen is already
+  /// in the stack, everything above it is dropped (going back to it),
+  /// otherwise it is pushed on top
+  pub fn show(&mut self, screen: Screen) {
+    if let Some(index) = self.screens.iter().position(|s| *s == screen) {
+      self.screens.truncate(index + 1);
+      return
+    }
+
+    if self.current() == Some(Screen::Loading) {
+      self.screens.pop();
+    }
+
+    self.screens.push(screen);
+  }
+
+  pub fn current(&self) -> Option<Screen> {
+    self.screens.last().cloned()
+  }
+
+  /// Get the screen that going back from the current screen would show
+  pub fn previous(&self) -> Option<Screen> {
+    if self.screens.len() < 2 {
+      None
+    }
+    else {
+      Some(self.screens[self.screens.len() - 2])
+    }
+  }
+
+  pub fn depth(&self) -> usize {
+    self.screens.len()
+  }
+}
+
+#[test]
+fn test_show_and_go_back() {
+  let mut stack = NavigationStack::default();
+
+  stack.show(Screen::MainMenu);
+  assert_eq!(stack.depth(), 1);
+  assert_eq!(stack.previous(), None);
+
+  stack.show(Screen::Game);
+  stack.show(Screen::Pause);
+  assert_eq!(stack.current(), Some(Screen::Pause));
+  assert_eq!(stack.previous(), Some(Screen::Game));
+
+  stack.show(Screen::Game);
+  assert_eq!(stack.depth(), 2);
+
+  stack.show(Screen::Pause);
+  stack.show(Screen::MainMenu);
+  assert_eq!(stack.current(), Some(Screen::MainMenu));
+  assert_eq!(stack.depth(), 1);
+}
@@ -0,0 +1,129 @@
+
+use model::Card;
+
+/// How the slots of the play area (the cards in play plus the gap for a
+/// card being dragged) are broken into rows.  Rows are broken in front of
+/// binary operators where possible, preferring breaks outside of
+/// parentheses so groups stay on one row
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayAreaLayout {
+  positions: Vec<(usize, usize)>,
+  row_lengths: Vec<usize>
+}
+
+impl PlayAreaLayout {
+
+  /// Lay out the given slots with at most `max_per_row` slots in each row.
+  /// Empty slots are gaps left for the card being dragged
+  pub fn new(slots: &[Option<Card>], max_per_row: usize) -> PlayAreaLayout {
+    let max_per_row = max_per_row.max(1);
+    let min_per_row = ((max_per_row + 1) / 2).max(1);
+    let depths = paren_depths(slots);
+
+    let mut result = PlayAreaLayout::default();
+    let mut row_start = 0;
+
+    while row_start < slots.len() {
+      let row_end = if slots.len() - row_start <= max_per_row {
+        slots.len()
+      }
+      else {
+        ((row_start + min_per_row)..(row_start + max_per_row + 1))
+            .filter(|b| is_binary_operator(&slots[*b]))
+            .min_by_key(|b| (depths[*b], -(*b as isize)))
+            .unwrap_or(row_start + max_per_row)
+      };
+
+      let row = result.row_lengths.len();
+
+      for col in 0..(row_end - row_start) {
+        result.positions.push((row, col));
+      }
+
+      result.row_lengths.push(row_end - row_start);
+      row_start = row_end;
+    }
+
+    result
+  }
+
+  pub fn row_count(&self) -> usize {
+    self.row_lengths.len()
+  }
+
+  pub fn row_length(&self, row: usize) -> usize {
+    self.row_lengths.get(row).cloned().unwrap_or(0)
+  }
+
+  /// Get the row and column of the slot at the given index
+  pub fn position_of(&self, slot: usize) -> Option<(usize, usize)> {
+    self.positions.get(slot).cloned()
+  }
+}
+
+fn is_binary_operator(slot: &Option<Card>) -> bool {
+  match *slot {
+    Some(Card::Plus)
+        | Some(Card::Minus)
+        | Some(Card::Times)
+        | Some(Card::Divide)
+        | Some(Card::Power) => true,
+    _ => false
+  }
+}
+
+/// Get the depth of parentheses in front of each slot
+fn paren_depths(slots: &[Option<Card>]) -> Vec<usize> {
+  let mut depth = 0usize;
+
+  slots.iter()
+      .map(|slot| {
+        let slot_depth = match *slot {
+          Some(Card::ParenR) => depth.saturating_sub(1),
+          _ => depth
+        };
+
+        depth = match *slot {
+          Some(Card::ParenL) => depth + 1,
+          Some(Card::ParenR) => depth.saturating_sub(1),
+          _ => depth
+        };
+
+        slot_depth
+      })
+      .collect()
+}
+
+#[cfg(test)]
+fn slots_for(tokens: &str) -> Vec<Option<Card>> {
+  tokens.chars()
+      .map(|c| if c == '_' { None } else { Card::from_key(c) })
+      .collect()
+}
+
+#[test]
+fn test_breaks_before_operators() {
+  let layout = PlayAreaLayout::new(&slots_for("4+4/4-4"), 4);
+
+  assert_eq!(layout.row_count(), 2);
+  assert_eq!(layout.row_length(0), 3);
+  assert_eq!(layout.position_of(3), Some((1, 0)));
+}
+
+#[test]
+fn test_keeps_groups_together() {
+  let layout = PlayAreaLayout::new(&slots_for("(4+4)*(4-4)"), 6);
+
+  assert_eq!(layout.row_count(), 2);
+  assert_eq!(layout.row_length(0), 5);
+  assert_eq!(layout.row_length(1), 6);
+}
+
+#[test]
+fn test_hard_break_without_operators() {
+  let layout = PlayAreaLayout::new(&slots_for("44_44"), 2);
+
+  assert_eq!(layout.row_count(), 3);
+  assert_eq!(layout.position_of(4), Some((2, 0)));
+  assert_eq!(PlayAreaLayout::new(&[], 3).row_count(), 0);
+}
@@ -0,0 +1,185 @@
+
+use itertools::Itertools;
+
+use model::{
+  Campaign,
+  Card,
+  Credit,
+  GameSetup,
+  GameState,
+  SetupOrigin
+};
+
+const CAMPAIGN_PREFIX : &str = "campaign";
+const SOLUTION_PREFIX : &str = "solution";
+
+/// Everything about a player's game that should survive restarting the
+/// application
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayerProgress {
+  campaigns: Vec<Campaign>
+}
+
+impl PlayerProgress {
+
+  pub fn campaigns(&self) -> &Vec<Campaign> {
+    &self.campaigns
+  }
+
+  pub fn campaign_for(&self, setup: &GameSetup) -> Option<&Campaign> {
+    self.campaigns.iter().find(|campaign| campaign.matches(setup))
+  }
+
+  /// Get the campaign played with the digits of the given setup, starting a
+  /// new one if there isn't one yet
+  pub fn campaign_for_mut(&mut self, setup: &GameSetup) -> &mut Campaign {
+    let existing_index = self.campaigns.iter()
+        .position(|campaign| campaign.matches(setup));
+
+    let index = match existing_index {
+      Some(index) => index,
+      None => {
+        self.campaigns.push(Campaign::for_setup(setup));
+        self.campaigns.len() - 1
+      }
+    };
+
+    &mut self.campaigns[index]
+  }
+
+  /// Create the game state for the next unreached goal of the campaign
+  /// played with the digits of the given setup
+  pub fn next_campaign_game(&self, setup: &GameSetup) -> GameState {
+    let campaign = self.campaign_for(setup)
+        .cloned()
+        .unwrap_or_else(|| Campaign::for_setup(setup));
+
+    let goal = campaign.next_unreached_goal()
+        .unwrap_or_else(|| campaign.min_goal());
+
+    campaign.game_state_for(goal)
+  }
+
+  /// Credit the campaign for the given setup with the value of the cards in
+  /// play.  Games that didn't come from a campaign aren't credited
+  pub fn credit(&mut self,
+      setup: &GameSetup,
+      value: f64,
+      cards: &Vec<Card>) -> Credit {
+    if setup.origin() != SetupOrigin::Campaign {
+      return Credit::Unchanged
+    }
+
+    self.campaign_for_mut(setup).credit(value, cards)
+  }
+
+  /// Serialize this progress into the line-based format used for saving
+  pub fn to_save_string(&self) -> String {
+    let mut lines : Vec<String> = Vec::new();
+
+    for campaign in &self.campaigns {
+      lines.push(format!("{} {} {} {}",
+          CAMPAIGN_PREFIX,
+          campaign.digits().iter().join(","),
+          campaign.min_goal(),
+          campaign.max_goal()));
+
+      for (goal, cards) in campaign.solutions() {
+        lines.push(format!("{} {} {}",
+            SOLUTION_PREFIX,
+            goal,
+            cards.iter().map(Card::to_token).join(" ")));
+      }
+    }
+
+    lines.join("\n")
+  }
+
+  /// Parse progress written by `to_save_string`
+  pub fn from_save_string(saved: &str) -> Result<PlayerProgress, String> {
+    let mut result = PlayerProgress::default();
+
+    for line in saved.lines().map(str::trim).filter(|l| !l.is_empty()) {
+      let mut parts = line.split_whitespace();
+
+      match parts.next() {
+        Some(CAMPAIGN_PREFIX) => {
+          let digits = parts.next()
+              .ok_or_else(|| format!("Missing digits: {}", line))?
+              .split(',')
+              .map(|d| d.parse::<i64>()
+                  .map_err(|_| format!("Invalid digit: {}", line)))
+              .collect::<Result<Vec<i64>, String>>()?;
+          let min_goal = parse_i64(parts.next(), line)?;
+          let max_goal = parse_i64(parts.next(), line)?;
+
+          result.campaigns.push(Campaign::new(digits, min_goal, max_goal));
+        },
+        Some(SOLUTION_PREFIX) => {
+          let goal = parse_i64(parts.next(), line)?;
+          let cards = parts
+              .map(|token| Card::from_token(token)
+                  .ok_or_else(|| format!("Invalid card: {}", line)))
+              .collect::<Result<Vec<Card>, String>>()?;
+
+          result.campaigns.last_mut()
+              .ok_or_else(|| format!("Solution before campaign: {}", line))?
+              .restore_solution(goal, cards);
+        },
+        _ => return Err(format!("Unrecognized progress line: {}", line))
+      }
+    }
+
+    Ok(result)
+  }
+}
+
+fn parse_i64(value: Option<&str>, line: &str) -> Result<i64, String> {
+  value
+      .and_then(|v| v.parse::<i64>().ok())
+      .ok_or_else(|| format!("Invalid number: {}", line))
+}
+
+#[test]
+fn test_save_round_trip() {
+  let mut progress = PlayerProgress::default();
+  let setup = GameSetup::classic();
+  let cards = vec![
+    required_number_card!(4),
+    Card::Divide,
+    required_number_card!(4),
+    Card::Inverse,
+    Card::Plus,
+    required_number_card!(4),
+    Card::Minus,
+    required_number_card!(4)
+  ];
+
+  assert_eq!(progress.credit(&setup, 16.5, &cards), Credit::Unchanged);
+  assert_eq!(progress.credit(&setup, 16.0, &cards), Credit::Reached(16));
+
+  let saved = progress.to_save_string();
+
+  assert_eq!(saved,
+      "campaign 4,4,4,4 0 100\nsolution 16 4 / 4 ^-1 + 4 - 4");
+  assert_eq!(PlayerProgress::from_save_string(&saved), Ok(progress));
+  assert!(PlayerProgress::from_save_string("solution 1 4").is_err());
+}
+
+#[test]
+fn test_daily_challenge_not_credited() {
+  let mut progress = PlayerProgress::default();
+  let setup = GameSetup::classic().with_origin(SetupOrigin::DailyChallenge);
+  let cards = vec![
+    required_number_card!(4),
+    Card::Divide,
+    required_number_card!(4),
+    Card::Plus,
+    required_number_card!(4),
+    Card::Minus,
+    required_number_card!(4)
+  ];
+
+  assert_eq!(progress.credit(&setup, 1.0, &cards), Credit::Unchanged);
+  assert!(progress.campaigns().is_empty());
+}
@@ -23,6 +23,28 @@ impl Rect {
     }
   }
 
+  /// Get a copy of this rect moved by the given amounts
+  pub fn offset(&self, dx: f64, dy: f64) -> Rect {
+    Rect::new(
+        self.top_left.x + dx,
+        self.top_left.y + dy,
+        self.size.width,
+        self.size.height)
+  }
+
+  /// Get a copy of this rect scaled by the given factor about its center
+  pub fn scaled(&self, factor: f64) -> Rect {
+    let center = self.center();
+    let width = self.size.width * factor;
+    let height = self.size.height * factor;
+
+    Rect::new(
+        center.x - width / 2.,
+        center.y - height / 2.,
+        width,
+        height)
+  }
+
   /// Get the minimum distance from this rect to the given point.  If the given
   /// point is within this rectangle then 0 is returned
   pub fn distance_to(&self, point: &Point) -> f64 {
@@ -82,6 +104,18 @@ fn test_contains() {
     assert_eq!(r.contains(&Point::new(-1.000001, 0.)), false);
 }
 
+#[test]
+fn test_scaled() {
+    let r = Rect::new(0., 0., 4., 2.).scaled(1.5);
+
+    assert_eq!((r.top_left.x, r.top_left.y), (-1., -0.5));
+    assert_eq!((r.size.width, r.size.height), (6., 3.));
+
+    let moved = r.offset(1., 2.);
+
+    assert_eq!((moved.top_left.x, moved.top_left.y), (0., 1.5));
+}
+
 #[test]
 fn test_distance() {
     let r = Rect::new(-1., -2., 3., 4.);
@@ -0,0 +1,262 @@
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use model::Card;
+
+/// Highest supply limit a game can have.  Supply cards show how many are
+/// left with a single digit, so larger limits can't be displayed
+pub const MAX_SUPPLY_LIMIT : usize = 9;
+
+/// Ways an arrangement of cards can break the rules of a game
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleViolation {
+  TooManyCards(usize),
+  OperatorNotAllowed(Card),
+  SupplyLimitExceeded(Card, usize),
+  SupplyLimitTooHigh(Card, usize),
+  RequiredCardUnused(Card),
+  Concatenation(usize)
+}
+
+impl Display for RuleViolation {
+  fn fmt(&self, format: &mut Formatter) -> fmt::Result {
+    use self::RuleViolation::*;
+    match *self {
+      TooManyCards(max) => write!(format, "No more than {} cards allowed", max),
+      OperatorNotAllowed(ref card) => {
+        write!(format, "{} is not allowed", card.to_token())
+      },
+      SupplyLimitExceeded(ref card, limit) => {
+        write!(format, "Only {} of {} allowed", limit, card.to_token())
+      },
+      SupplyLimitTooHigh(ref card, limit) => {
+        write!(format, "The limit of {} {} is more than {}",
+            limit, card.to_token(), MAX_SUPPLY_LIMIT)
+      },
+      RequiredCardUnused(ref card) => {
+        write!(format, "{} must be used", card.to_token())
+      },
+      Concatenation(ord) => {
+        write!(format, "Numbers cannot be joined (at card {})", ord)
+      }
+    }
+  }
+}
+
+/// The rules a game is played by.  The defaults match the classic game: all
+/// required cards must be used, every operator is allowed and there is no
+/// limit on supply cards
+#[derive(Debug, Clone, PartialEq, Getters, Setters)]
+pub struct Rules {
+
+  /// Most copies of each supply card that can be in play at once, up to
+  /// `MAX_SUPPLY_LIMIT`.  Cards not listed are unlimited
+  #[get = "pub"] supply_limits: Vec<(Card, usize)>,
+
+  #[get = "pub"] #[set = "pub"] require_all_cards: bool,
+  #[get = "pub"] #[set = "pub"] allow_concatenation: bool,
+  #[get = "pub"] #[set = "pub"] max_card_count: Option<usize>,
+  #[get = "pub"] #[set = "pub"] allowed_operators: Vec<Card>
+}
+
+impl Default for Rules {
+  fn default() -> Rules {
+    Rules {
+      supply_limits: Vec::default(),
+      require_all_cards: true,
+      allow_concatenation: true,
+      max_card_count: None,
+      allowed_operators: all_non_number_cards!()
+    }
+  }
+}
+
+impl Rules {
+
+  pub fn supply_limit(&self, card: &Card) -> Option<usize> {
+    self.supply_limits.iter()
+        .find(|&&(ref limited, _)| limited == card)
+        .map(|&(_, limit)| limit)
+  }
+
+  pub fn set_supply_limit(&mut self, card: Card, limit: usize) {
+    self.supply_limits.retain(|&(ref limited, _)| limited != &card);
+    self.supply_limits.push((card, limit));
+  }
+
+  pub fn is_operator_allowed(&self, card: &Card) -> bool {
+    match card {
+      Card::Number(_, _) => true,
+      _ => self.allowed_operators.contains(card)
+    }
+  }
+
+  /// Check whether the given card could be added to the given cards in play
+  /// without breaking a limit.  This does not check the arrangement of the
+  /// cards, only the counts
+  pub fn check_add(&self, card: &Card, cards_in_play: &Vec<Card>)
+      -> Result<(), RuleViolation> {
+
+    if let Some(max) = self.max_card_count {
+      if cards_in_play.len() >= max {
+        return Err(RuleViolation::TooManyCards(max))
+      }
+    }
+
+    if !self.is_operator_allowed(card) {
+      return Err(RuleViolation::OperatorNotAllowed(card.clone()))
+    }
+
+    if let Some(limit) = self.supply_limit(card) {
+      if count_of(card, cards_in_play) >= limit {
+        return Err(RuleViolation::SupplyLimitExceeded(card.clone(), limit))
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Check the given arrangement of cards against all of these rules.  No
+  /// arrangement is valid if a supply limit is above `MAX_SUPPLY_LIMIT`
+  pub fn validate(&self,
+      cards_in_play: &Vec<Card>,
+      required_cards: &Vec<Card>) -> Result<(), RuleViolation> {
+
+    if let Some(max) = self.max_card_count {
+      if cards_in_play.len() > max {
+        return Err(RuleViolation::TooManyCards(max))
+      }
+    }
+
+    for card in cards_in_play {
+      if !self.is_operator_allowed(card) {
+        return Err(RuleViolation::OperatorNotAllowed(card.clone()))
+      }
+    }
+
+    for &(ref card, limit) in &self.supply_limits {
+      if limit > MAX_SUPPLY_LIMIT {
+        return Err(RuleViolation::SupplyLimitTooHigh(card.clone(), limit))
+      }
+
+      if count_of(card, cards_in_play) > limit {
+        return Err(RuleViolation::SupplyLimitExceeded(card.clone(), limit))
+      }
+    }
+
+    if self.require_all_cards {
+      for card in required_cards {
+        if count_of(card, cards_in_play) < count_of(card, required_cards) {
+          return Err(RuleViolation::RequiredCardUnused(card.clone()))
+        }
+      }
+    }
+
+    if !self.allow_concatenation {
+      for ord in 1..cards_in_play.len() {
+        if is_number(&cards_in_play[ord - 1]) && is_number(&cards_in_play[ord]) {
+          return Err(RuleViolation::Concatenation(ord))
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Get the required cards that aren't in play, if the rules require all
+  /// cards to be used.  Each card is listed once no matter how many copies
+  /// are missing
+  pub fn unused_required_cards(&self,
+      cards_in_play: &Vec<Card>,
+      required_cards: &Vec<Card>) -> Vec<Card> {
+    if !self.require_all_cards {
+      return Vec::default()
+    }
+
+    let mut result : Vec<Card> = Vec::default();
+
+    for card in required_cards {
+      if count_of(card, cards_in_play) < count_of(card, required_cards)
+          && !result.contains(card) {
+        result.push(card.clone());
+      }
+    }
+
+    result
+  }
+}
+
+fn is_number(card: &Card) -> bool {
+  match card {
+    Card::Number(_, _) => true,
+    _ => false
+  }
+}
+
+fn count_of(card: &Card, cards: &Vec<Card>) -> usize {
+  cards.iter().filter(|c| *c == card).count()
+}
+
+#[test]
+fn test_validate() {
+  let required = vec![required_number_card!(4), required_number_card!(4)];
+  let mut rules = Rules::default();
+
+  let joined = vec![required_number_card!(4), required_number_card!(4)];
+  let added = vec![
+      required_number_card!(4), Card::Plus, required_number_card!(4)];
+  let unused = vec![required_number_card!(4), Card::Factorial];
+
+  assert_eq!(rules.validate(&joined, &required), Ok(()));
+  assert_eq!(rules.validate(&unused, &required),
+      Err(RuleViolation::RequiredCardUnused(required_number_card!(4))));
+
+  rules.set_allow_concatenation(false);
+  assert_eq!(rules.validate(&joined, &required),
+      Err(RuleViolation::Concatenation(1)));
+
+  rules.set_allowed_operators(vec![Card::Minus]);
+  assert_eq!(rules.validate(&added, &required),
+      Err(RuleViolation::OperatorNotAllowed(Card::Plus)));
+
+  rules.set_require_all_cards(false);
+  rules.set_allowed_operators(all_non_number_cards!());
+  assert_eq!(rules.validate(&unused, &required), Ok(()));
+
+  rules.set_supply_limit(Card::Factorial, MAX_SUPPLY_LIMIT + 1);
+  assert_eq!(rules.validate(&unused, &required),
+      Err(RuleViolation::SupplyLimitTooHigh(
+          Card::Factorial, MAX_SUPPLY_LIMIT + 1)));
+}
+
+#[test]
+fn test_check_add() {
+  let mut rules = Rules::default();
+  let in_play = vec![required_number_card!(4), Card::Radical];
+
+  assert_eq!(rules.check_add(&Card::Radical, &in_play), Ok(()));
+
+  rules.set_supply_limit(Card::Radical, 1);
+  assert_eq!(rules.check_add(&Card::Radical, &in_play),
+      Err(RuleViolation::SupplyLimitExceeded(Card::Radical, 1)));
+
+  rules.set_max_card_count(Some(2));
+  assert_eq!(rules.check_add(&Card::Plus, &in_play),
+      Err(RuleViolation::TooManyCards(2)));
+}
+
+#[test]
+fn test_unused_required_cards() {
+  let mut rules = Rules::default();
+  let required = vec![
+      required_number_card!(4), required_number_card!(4), Card::Radical];
+  let in_play = vec![required_number_card!(4), Card::Plus];
+
+  assert_eq!(rules.unused_required_cards(&in_play, &required),
+      vec![required_number_card!(4), Card::Radical]);
+  assert_eq!(rules.unused_required_cards(&required, &required), vec![]);
+
+  rules.set_require_all_cards(false);
+  assert_eq!(rules.unused_required_cards(&in_play, &required), vec![]);
+}
@@ -2,7 +2,7 @@ pub use self::has_int_size::HasIntSize;
 pub use self::system_view::SystemView;
 pub use self::texture_loader::TextureLoader;
 pub use self::texture::Texture;
-pub use self::textures::Textures;
+pub use self::textures::{TextureSet, Textures};
 pub use self::runtime_resources::RuntimeResources;
 
 mod has_int_size;
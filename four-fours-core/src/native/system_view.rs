@@ -11,4 +11,10 @@ pub trait SystemView : 'static + Sized {
 
 
   fn get_texture_loader(&self) -> Self::TL;
-}
+
+  /// Get the player progress last stored with `save_progress` or an empty
+  /// string if nothing has been saved
+  fn load_progress(&self) -> String;
+
+  fn save_progress(&self, progress: String);
+}
\ No newline at end of file
@@ -1,4 +1,6 @@
 
+use std::sync::{Arc, PoisonError, RwLock};
+
 use native::{Texture, TextureLoader};
 
 macro_rules! count {
@@ -96,22 +98,43 @@ define_texture_atlas!(Symbols (x_tile_count: 6, y_tile_count: 6) {
   decimal(left: 2, top: 3, width: 1, height: 1)
 });
 
-pub struct Textures<T: Texture> {
+impl <T: Texture> Symbols<T> {
+
+  /// Get the texture for the given single digit number
+  pub fn number(&self, val: i64) -> Option<&T> {
+    match val {
+      0 => Some(self.zero()),
+      1 => Some(self.one()),
+      2 => Some(self.two()),
+      3 => Some(self.three()),
+      4 => Some(self.four()),
+      5 => Some(self.five()),
+      6 => Some(self.six()),
+      7 => Some(self.seven()),
+      8 => Some(self.eight()),
+      9 => Some(self.nine()),
+      _ => None
+    }
+  }
+}
+
+/// The sub-textures cut from the loaded texture atlases
+pub struct TextureSet<T: Texture> {
   card: T,
   symbols: Symbols<T>
 }
 
-impl <T: Texture> Textures<T> {
+impl <T: Texture> TextureSet<T> {
 
-  pub fn new(texture_loader: &TextureLoader<T = T>, progress_callback: &Fn(f64))
-      -> Textures<T> {
+  fn load(texture_loader: &TextureLoader<T = T>, progress_callback: &Fn(f64))
+      -> TextureSet<T> {
 
     let card_atlas = Card::new(texture_loader.load_texture(
         String::from("Card.png")), |p| progress_callback(p / 2.));
     let symbols_atlas = Symbols::new(texture_loader.load_texture(
         String::from("Symbols.png")), |p| progress_callback(0.5 + p / 2.));
 
-    Textures{
+    TextureSet{
       card: card_atlas.card,
       symbols: symbols_atlas
     }
@@ -125,4 +148,82 @@ impl <T: Texture> Textures<T> {
     &self.symbols
   }
 
-}
\ No newline at end of file
+}
+
+/// Holds the game's textures and the loader they came from, so they can be
+/// unloaded when memory runs low and loaded again the next time they're
+/// needed
+pub struct Textures<T: Texture> {
+  texture_loader: Box<TextureLoader<T = T>>,
+  loaded: RwLock<Option<Arc<TextureSet<T>>>>
+}
+
+impl <T: Texture> Textures<T> {
+
+  pub fn new(texture_loader: Box<TextureLoader<T = T>>,
+      progress_callback: &Fn(f64)) -> Textures<T> {
+    let loaded = TextureSet::load(&*texture_loader, progress_callback);
+
+    Textures {
+      texture_loader: texture_loader,
+      loaded: RwLock::new(Some(Arc::new(loaded)))
+    }
+  }
+
+  /// Get the sub-textures, loading them again if they have been unloaded
+  pub fn get(&self) -> Arc<TextureSet<T>> {
+    if let Some(ref loaded) = *self.loaded.read()
+        .unwrap_or_else(PoisonError::into_inner) {
+      return loaded.clone()
+    }
+
+    let mut loaded_guard = self.loaded.write()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    // Another thread may have reloaded them while waiting for the lock
+    if let Some(ref loaded) = *loaded_guard {
+      return loaded.clone()
+    }
+
+    info!("Reloading unloaded textures");
+
+    let loaded = Arc::new(TextureSet::load(&*self.texture_loader, &|_| {}));
+
+    *loaded_guard = Some(loaded.clone());
+    loaded
+  }
+
+  /// Release the sub-textures.  Sprites already showing them keep their
+  /// textures, but nothing else is held until they're needed again.
+  /// Returns whether they were loaded
+  pub fn unload(&self) -> bool {
+    self.loaded.write().unwrap_or_else(PoisonError::into_inner)
+        .take()
+        .is_some()
+  }
+
+  pub fn is_loaded(&self) -> bool {
+    self.loaded.read().unwrap_or_else(PoisonError::into_inner).is_some()
+  }
+}
+
+#[cfg(test)]
+use headless::HeadlessTextureLoader;
+
+#[test]
+fn test_unload_and_reload() {
+  let textures = Textures::new(Box::new(HeadlessTextureLoader), &|_| {});
+  let before = textures.get();
+
+  assert!(textures.is_loaded());
+  assert!(textures.unload());
+  assert!(!textures.is_loaded());
+  assert!(!textures.unload());
+
+  let after = textures.get();
+
+  assert!(textures.is_loaded());
+  assert!(!Arc::ptr_eq(&before, &after));
+  assert_eq!(after.symbols().plus(), before.symbols().plus());
+  assert!(Arc::ptr_eq(&after, &textures.get()));
+}
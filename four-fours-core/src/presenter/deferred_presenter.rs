@@ -0,0 +1,48 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Holds a presenter that may not be created until something it needs is
+/// ready, like the runtime resources.  Lets a view be bound straight away
+/// and get its presenter later
+pub struct DeferredPresenter<P> {
+  presenter: Mutex<Option<Arc<P>>>
+}
+
+impl <P> DeferredPresenter<P> {
+
+  pub fn ready(presenter: Arc<P>) -> Arc<DeferredPresenter<P>> {
+    Arc::new(DeferredPresenter {
+      presenter: Mutex::new(Some(presenter))
+    })
+  }
+
+  pub fn pending() -> Arc<DeferredPresenter<P>> {
+    Arc::new(DeferredPresenter {
+      presenter: Mutex::new(None)
+    })
+  }
+
+  pub fn set(&self, presenter: Arc<P>) {
+    *self.presenter.lock().unwrap_or_else(PoisonError::into_inner)
+        = Some(presenter);
+  }
+
+  /// Get the presenter if it has been created
+  pub fn get(&self) -> Option<Arc<P>> {
+    self.presenter.lock().unwrap_or_else(PoisonError::into_inner).clone()
+  }
+
+  pub fn is_ready(&self) -> bool {
+    self.get().is_some()
+  }
+}
+
+#[test]
+fn test_pending_until_set() {
+  let deferred = DeferredPresenter::pending();
+
+  assert!(!deferred.is_ready());
+
+  deferred.set(Arc::new(3));
+
+  assert_eq!(deferred.get(), Some(Arc::new(3)));
+}
@@ -6,11 +6,14 @@ use std::sync::{
 };
 
 use std::sync::atomic::{
-  AtomicIsize
+  AtomicBool,
+  AtomicIsize,
+  AtomicUsize,
+  Ordering
 };
 
 use std::{
-  thread
+  f64
 };
 
 use std::time::{
@@ -18,18 +21,34 @@ use std::time::{
   Duration
 };
 
+use animation::{
+  Animation,
+  AnimationId,
+  Animator,
+  Easing,
+  Tween
+};
+
 use event::{
+  AnimationFrame,
+  EnterBackground,
+  EnterForeground,
   EventBus,
   EventListener,
   ListenerRegistration,
-  FourFoursEvent,
   Layout,
-  Evaluate
+  Evaluate,
+  EvaluatePreview,
+  PauseGame,
+  Terminate
 };
 
 use math::{
   MathEngine,
-  MathResponse
+  MathResponse,
+  MathResult,
+  TexElement,
+  TexLayout
 };
 
 use model::{
@@ -38,28 +57,52 @@ use model::{
   Rect,
   GameState,
   Card,
+  Credit,
   GameSetup,
   GameDisplayState,
-  DraggedCardDisplayState
+  DraggedCardDisplayState,
+  PlayerProgress,
+  PlayAreaLayout
 };
 
+use replay::InputRecorder;
+
 use native::{
   HasIntSize,
   RuntimeResources,
-  SystemView
+  SystemView,
+  Texture
 };
 
 use ui::{
+  CardVisualState,
+  ClickHandler,
   GameView,
+  HasClickHandlers,
   LayoutHandler,
   HandlerRegistration,
   HasMutableVisibility,
+  HasTexDisplay,
+  HasText,
+  HasKeyHandlers,
+  Sprite,
   UiCard,
-  DragHandler
+  DragHandler,
+  Key,
+  KeyHandler
 };
 
 lazy_static!{
   static ref MIN_EVAL_SEPARATION : Duration = Duration::from_millis(500);
+  static ref MAX_TAP_DURATION : Duration = Duration::from_millis(300);
+
+  static ref FRAME_INTERVAL : Duration = Duration::from_millis(16);
+  static ref SHAKE_DURATION : Duration = Duration::from_millis(350);
+  static ref SUPPLY_ENTER_DURATION : Duration = Duration::from_millis(400);
+  static ref SUPPLY_ENTER_STAGGER : Duration = Duration::from_millis(60);
+  static ref CELEBRATE_GROW_DURATION : Duration = Duration::from_millis(150);
+  static ref CELEBRATE_SETTLE_DURATION : Duration = Duration::from_millis(300);
+  static ref CELEBRATE_STAGGER : Duration = Duration::from_millis(80);
 }
 
 const BOUNDARY_FRACTION : f64 = 0.04;
@@ -72,6 +115,44 @@ const MIN_PLAY_CARD_WIDTH_PTS : f64 = 35.0;
 
 const TEX_AREA_HEIGHT_FRAC : f64 = 0.3;
 
+/// Farthest a press can move and still count as a tap rather than a drag
+const MAX_TAP_DISTANCE_PTS : f64 = 10.0;
+
+const SOLVED_STATUS : &str = "Solved!";
+
+/// How long cards in play take to slide to new positions
+const PLAY_CARD_MOVE_SECS : f64 = 0.1;
+
+/// How far an invalid card shakes side to side as a fraction of its width
+const SHAKE_AMPLITUDE_FRAC : f64 = 0.15;
+const SHAKE_CYCLES : f64 = 3.;
+
+/// How much each card in play grows when the puzzle is solved
+const CELEBRATE_SCALE : f64 = 0.25;
+
+/// Where and when the current press started, how far it has moved, and
+/// when it was released
+struct Gesture {
+  start: Point,
+  start_time: Instant,
+  end_time: Option<Instant>,
+  max_distance: f64
+}
+
+impl Gesture {
+
+  /// True if the press is (or was, once released) short and still enough
+  /// to be a tap rather than a drag
+  fn is_tap(&self) -> bool {
+    let duration = match self.end_time {
+      Some(end_time) => end_time.duration_since(self.start_time),
+      None => self.start_time.elapsed()
+    };
+
+    self.max_distance <= MAX_TAP_DISTANCE_PTS && duration <= *MAX_TAP_DURATION
+  }
+}
+
 pub struct GamePresenter<V,S>
     where
         S: SystemView,
@@ -83,13 +164,81 @@ pub struct GamePresenter<V,S>
   handler_registrations: Mutex<Vec<Box<HandlerRegistration>>>,
 
   display_state: RwLock<GameDisplayState<V::S>>,
+  gesture: Mutex<Option<Gesture>>,
   math_engine: MathEngine,
+  tex_layout: Mutex<TexLayout>,
   goal: AtomicIsize,
-
-  last_eval: Mutex<Option<String>>,
-  eval_queue: Mutex<Option<String>>,
+  setup: GameSetup,
+
+  progress: Arc<RwLock<PlayerProgress>>,
+  progress_sink: Box<Fn()>,
+  game_state_sink: Box<Fn(GameState)>,
+  recorder: Arc<InputRecorder>,
+
+  last_eval: Mutex<Option<(String, MathResponse)>>,
+  eval_queue: Mutex<Option<(String, Vec<Card>)>>,
+  eval_generation: AtomicUsize,
+  preview_queue: Mutex<Option<Vec<Card>>>,
+  preview_generation: AtomicUsize,
   next_eval_time: Mutex<Option<Instant>>,
-  last_eval_time: Mutex<Instant>
+  last_eval_time: Mutex<Instant>,
+
+  /// Animations are run against the display state, which is locked once
+  /// per frame rather than by each animation
+  animator: Animator<GameDisplayState<V::S>>,
+  /// Time of the last animation frame on the event bus's clock, or none if
+  /// no frame is scheduled
+  last_frame_time: Mutex<Option<Instant>>,
+  supply_entered: AtomicBool,
+  solved: AtomicBool,
+  celebration: Mutex<Vec<AnimationId>>,
+  /// Whether the app is in the background, where nothing is drawn
+  suspended: AtomicBool
+}
+
+impl <V,S> EventListener<AnimationFrame> for GamePresenter<V,S>
+    where
+        S: SystemView,
+        V: GameView<T = S::T> {
+  fn on_event(&self, _: &AnimationFrame) {
+    {
+      let display_state = self.display_state.read()
+          .expect("Failed to get read lock on display state");
+
+      if self.suspended.load(Ordering::SeqCst) {
+        // Nothing is drawn in the background, so skip straight to the end
+        self.animator.finish_all(&*display_state);
+      }
+      else {
+        let delta = {
+          let mut last_frame_time = self.last_frame_time.lock()
+              .expect("Failed to lock last frame time");
+          let now = self.event_bus.now();
+          let delta = last_frame_time.map(|t| now - t).unwrap_or_default();
+
+          *last_frame_time = Some(now);
+          delta
+        };
+
+        self.animator.advance(&*display_state, delta);
+      }
+    }
+
+    let needs_frame = {
+      let mut last_frame_time = self.last_frame_time.lock()
+          .expect("Failed to lock last frame time");
+
+      if !self.animator.is_running() {
+        *last_frame_time = None;
+      }
+
+      last_frame_time.is_some()
+    };
+
+    if needs_frame {
+      self.event_bus.post_after(*FRAME_INTERVAL, AnimationFrame{});
+    }
+  }
 }
 
 impl <V,S> EventListener<Evaluate> for GamePresenter<V,S>
@@ -97,21 +246,48 @@ impl <V,S> EventListener<Evaluate> for GamePresenter<V,S>
         S: SystemView,
         V: GameView<T = S::T> {
   fn on_event(&self, _: &Evaluate) {
-    let mut to_eval_opt : Option<String> = None;
+    let to_eval_opt : Option<(String, Vec<Card>)>;
+    let generation : usize;
 
     {
-      to_eval_opt
-          = self.eval_queue.lock().expect("Failed to lock eval queue").take();
+      let mut q_lock = self.eval_queue.lock()
+          .expect("Failed to lock eval queue");
+
+      to_eval_opt = q_lock.take();
+      generation = self.eval_generation.load(Ordering::SeqCst);
+
+      *self.next_eval_time.lock().expect("Failed to lock next eval time")
+          = None;
     }
 
     info!("Handling evaluate event {:?}", to_eval_opt);
     match to_eval_opt {
-      Some(to_eval) => {
-        match (self.math_engine.evaluate(&to_eval)) {
+      Some((to_eval, cards)) => {
+        let result = self.evaluate(&to_eval, &cards);
+
+        if generation != self.eval_generation.load(Ordering::SeqCst) {
+          info!("Discarding out-of-date evaluation of {}", to_eval);
+          return
+        }
+
+        match result {
           Ok(resp) => {
             info!("{} = {}", resp.tex, resp.value);
+            self.recorder.record_state(
+                format!("{} = {}", to_eval, resp.value));
+            self.set_tex(&format!("{} = {}", resp.tex, resp.value));
+            self.show_error_card(&cards, None);
+            self.update_hud(Some(resp.value));
+            self.credit_campaign(resp.value, &cards);
           },
-          _ => { info!("Failed to parse as math"); }
+          Err(e) => {
+            info!("Failed to evaluate {}: {}", to_eval, e);
+            self.recorder.record_state(format!("{} failed", to_eval));
+            self.set_tex("");
+            self.show_error_card(&cards,
+                self.math_engine.find_error_card(&cards, &self.setup));
+            self.update_hud(None);
+          }
         }
       }
       _ => ()
@@ -120,6 +296,98 @@ impl <V,S> EventListener<Evaluate> for GamePresenter<V,S>
   }
 }
 
+impl <V,S> EventListener<EvaluatePreview> for GamePresenter<V,S>
+    where
+        S: SystemView,
+        V: GameView<T = S::T> {
+  fn on_event(&self, _: &EvaluatePreview) {
+    let cards_opt : Option<Vec<Card>>;
+    let generation : usize;
+
+    {
+      let mut q_lock = self.preview_queue.lock()
+          .expect("Failed to lock preview queue");
+
+      cards_opt = q_lock.take();
+      generation = self.preview_generation.load(Ordering::SeqCst);
+    }
+
+    if let Some(cards) = cards_opt {
+      let result = self.math_engine.evaluate_cards(&cards, &self.setup);
+
+      if generation != self.preview_generation.load(Ordering::SeqCst) {
+        info!("Discarding cancelled preview evaluation");
+        return
+      }
+
+      match result {
+        Ok(resp) => {
+          self.set_preview_tex(
+              Some(&format!("{} = {}", resp.tex, resp.value)),
+              generation);
+        },
+        Err(_) => self.set_preview_tex(None, generation)
+      }
+    }
+  }
+}
+
+impl <V,S> EventListener<PauseGame> for GamePresenter<V,S>
+    where
+        S: SystemView,
+        V: GameView<T = S::T> {
+  fn on_event(&self, _: &PauseGame) {
+    (self.game_state_sink)(self.current_game_state());
+
+    self.teardown();
+    self.view.transition_to_pause_view();
+  }
+}
+
+impl <V,S> EventListener<EnterBackground> for GamePresenter<V,S>
+    where
+        S: SystemView,
+        V: GameView<T = S::T> {
+  fn on_event(&self, _: &EnterBackground) {
+    self.suspend();
+  }
+}
+
+impl <V,S> EventListener<EnterForeground> for GamePresenter<V,S>
+    where
+        S: SystemView,
+        V: GameView<T = S::T> {
+  fn on_event(&self, _: &EnterForeground) {
+    if !self.suspended.swap(false, Ordering::SeqCst) {
+      return
+    }
+
+    info!("Resuming game");
+
+    let mut display_state = self.display_state.write()
+        .expect("Failed to lock display state for writing");
+
+    // Nothing to restore if the view was never laid out
+    if display_state.size().width <= 0. {
+      return
+    }
+
+    self.update_supply_cards(&mut *display_state);
+    self.layout_play_area_cards(&mut *display_state, 0.);
+    self.show_tex(display_state.tex_area_rect());
+  }
+}
+
+impl <V,S> EventListener<Terminate> for GamePresenter<V,S>
+    where
+        S: SystemView,
+        V: GameView<T = S::T> {
+  fn on_event(&self, _: &Terminate) {
+    self.suspend();
+    self.teardown();
+  }
+}
+
 impl <V,S> EventListener<Layout> for GamePresenter<V,S>
     where
         S: SystemView,
@@ -134,7 +402,7 @@ impl <V,S> EventListener<Layout> for GamePresenter<V,S>
     let height = event.height as f64;
 
     let card_aspect_ratio
-        = self.runtime_resources.textures().card().get_aspect_ratio();
+        = self.runtime_resources.textures().get().card().get_aspect_ratio();
 
     display_state.size_mut().width = width;
     display_state.size_mut().height = height;
@@ -220,6 +488,9 @@ impl <V,S> EventListener<Layout> for GamePresenter<V,S>
       display_state.set_tex_area_rect(tex_area_rect);
     }
 
+    let entering = !display_state.supply_cards().is_empty()
+        && !self.supply_entered.swap(true, Ordering::SeqCst);
+
     display_state
         .supply_cards()
         .iter()
@@ -227,11 +498,42 @@ impl <V,S> EventListener<Layout> for GamePresenter<V,S>
         .for_each(|(i, card)| {
 
           info!("index: {}", i);
-          card.set_rect(
-              &display_state.get_supply_card_rect_by_index(&i).unwrap());
+          let rect = display_state.get_supply_card_rect_by_index(&i).unwrap();
+
+          if entering {
+            card.set_rect(&rect.offset(0., height - rect.top_left.y));
+          }
+          else {
+            card.set_rect(&rect);
+          }
           card.set_visible(true);
         });
+
+    if entering {
+      self.animate_supply_cards_in(display_state.supply_cards().len());
+    }
+
     self.layout_play_area_cards(&mut *display_state, 0.);
+    self.show_tex(display_state.tex_area_rect());
+  }
+}
+
+/// Scale the card in play at the given index about the center of its slot
+fn scale_play_card<S,T>(display_state: &GameDisplayState<S>,
+    i: usize,
+    factor: f64)
+    where
+        T: Texture,
+        S: Sprite<T = T> {
+
+  if display_state.card_in_flight().is_some() {
+    return
+  }
+
+  if let (Some(card), Some(rect)) = (
+      display_state.cards_in_play().get(i),
+      display_state.get_play_card_rect_by_index(&i)) {
+    card.set_rect(&rect.scaled(factor));
   }
 }
 
@@ -244,6 +546,8 @@ impl <V,S> GamePresenter<V,S>
       display_state: &mut GameDisplayState<V::S>,
       animation_time_secs: f64) {
 
+    self.cancel_celebration();
+
     let drag_card_ord = match display_state.card_in_flight() {
       Some(drag_state) => {
         drag_state.play_area_ord().as_ref().cloned()
@@ -277,7 +581,18 @@ impl <V,S> GamePresenter<V,S>
         = ((available_play_area_width + min_play_card_spacing)
             / (play_card_width + min_play_card_spacing) + 0.1).floor();
 
-    let play_area_row_count = (play_card_slots / play_cards_per_row).ceil();
+    let mut slots : Vec<Option<Card>> = display_state.cards_in_play().iter()
+        .map(|ui_card| Some(ui_card.card().clone()))
+        .collect();
+
+    if let Some(drag_ord) = drag_card_ord {
+      slots.insert(drag_ord.min(slots.len()), None);
+    }
+
+    let play_area_layout
+        = PlayAreaLayout::new(&slots, play_cards_per_row as usize);
+
+    let play_area_row_count = play_area_layout.row_count() as f64;
 
     let play_card_spacing
         = if play_cards_per_row > 1.0 {
@@ -308,6 +623,7 @@ impl <V,S> GamePresenter<V,S>
     display_state.set_play_card_slots(play_card_slots);
 
     display_state.set_play_area_row_count(play_area_row_count);
+    display_state.set_play_area_layout(play_area_layout);
     display_state.play_area_rect_mut().top_left.x = first_play_card_left;
     display_state.play_area_rect_mut().top_left.y = first_play_card_top;
     display_state.play_area_rect_mut().size.width = play_area_width;
@@ -316,7 +632,7 @@ impl <V,S> GamePresenter<V,S>
     let mut bind_points : Vec<(f64,Vec<(f64,usize)>)> = Vec::default();
 
     for i in 0..(play_card_slots as usize) {
-      let row : usize = i / (play_cards_per_row as usize);
+      let (row, _) = display_state.play_area_layout().position_of(i).unwrap();
 
       let card_index_opt = match drag_card_ord {
         None => Some(i),
@@ -369,6 +685,66 @@ impl <V,S> GamePresenter<V,S>
     }
   }
 
+  /// Get the game state matching what is currently in play
+  fn current_game_state(&self) -> GameState {
+    let display_state = self.display_state.read()
+        .expect("Failed to get read lock on display state");
+
+    GameState::new_with_cards_in_play(
+        self.setup.clone(),
+        display_state.cards())
+  }
+
+  /// Stop moving things around while the app is in the background and hand
+  /// off the game in play and the player's progress to be saved.  A card
+  /// being dragged is dropped where it is since the end of the drag may
+  /// never arrive
+  fn suspend(&self) {
+    if self.suspended.swap(true, Ordering::SeqCst) {
+      return
+    }
+
+    info!("Suspending game");
+
+    let dragging = self.display_state.read()
+        .expect("Failed to get read lock on display state")
+        .card_in_flight().is_some();
+
+    if dragging {
+      self.on_drag_end();
+    }
+
+    self.cancel_preview_evaluation();
+    self.animator.finish_all(&*self.display_state.read()
+        .expect("Failed to get read lock on display state"));
+
+    (self.game_state_sink)(self.current_game_state());
+    (self.progress_sink)();
+  }
+
+  /// Release everything binding this presenter to its view and the event
+  /// bus.  The view's handlers hold references to this presenter, so it can
+  /// only be dropped after this is called
+  fn teardown(&self) {
+    info!("Tearing down game presenter");
+
+    self.cancel_preview_evaluation();
+    self.animator.cancel_all();
+
+    {
+      *self.eval_queue.lock().expect("Failed to lock eval queue") = None;
+      self.eval_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    if let Ok(mut locked_list) = self.handler_registrations.lock() {
+      locked_list.drain(..).for_each(|hr| hr.deregister());
+    }
+
+    if let Ok(mut locked_list) = self.listener_registrations.lock() {
+      locked_list.clear();
+    }
+  }
+
   fn on_play_card_drag_start(&self,
       ord: usize,
       drag_point_in_card: &Point,
@@ -414,13 +790,257 @@ impl <V,S> GamePresenter<V,S>
         card.clone(),
         play_area_ord,
         required_play_card,
-        self.runtime_resources.textures(),
+        &self.runtime_resources.textures().get(),
         &self.view)
   }
 
+  /// Creates a ui card for the supply area based on the given card
+  fn create_supply_ui_card(&self, card: &Card) -> UiCard<V::S> {
+    UiCard::new_supply_card(
+        card.clone(),
+        self.setup.rules().supply_limit(card),
+        &self.runtime_resources.textures().get(),
+        &self.view)
+  }
+
+  /// Update the remaining count shown on each supply card with a limited
+  /// supply based on the cards currently in use, and dim the cards that
+  /// can't be added.  A required card being dragged is highlighted while it
+  /// would be left out of play, since it has to be used
+  fn update_supply_cards(&self, display_state: &mut GameDisplayState<V::S>) {
+    let textures = self.runtime_resources.textures().get();
+    let rules = self.setup.rules();
+    let cards = display_state.cards();
+    let unused
+        = rules.unused_required_cards(&cards, self.setup.required_cards());
+
+    let updates : Vec<(Option<usize>, CardVisualState)>
+        = display_state.supply_cards().iter()
+            .map(|ui_card| {
+              let card = ui_card.card();
+              let remaining = rules.supply_limit(card).map(|limit| {
+                limit.saturating_sub(display_state.count_in_use(card))
+              });
+
+              let state = if rules.check_add(card, &cards).is_err() {
+                CardVisualState::Dimmed
+              }
+              else {
+                CardVisualState::Normal
+              };
+
+              (remaining, state)
+            })
+            .collect();
+
+    for (ui_card, (remaining, state))
+        in display_state.supply_cards_mut().iter_mut().zip(updates) {
+      if let Some(remaining) = remaining {
+        ui_card.set_remaining_count(remaining, &textures);
+      }
+
+      ui_card.set_visual_state(state);
+    }
+
+    if let Some(drag_state) = display_state.card_in_flight_mut().as_mut() {
+      let left_out = drag_state.play_area_ord().is_none()
+          && unused.contains(drag_state.card().card());
+
+      drag_state.card_mut().set_visual_state(if left_out {
+        CardVisualState::Highlighted
+      }
+      else {
+        CardVisualState::Normal
+      });
+    }
+  }
+
+  /// Mark the card in play at the given index as the one where the
+  /// expression goes wrong, and clear the mark from every other card.  This
+  /// does nothing if the cards in play are no longer the given cards
+  fn show_error_card(&self, cards: &Vec<Card>, error_ord: Option<usize>) {
+    let mut display_state = self.display_state.write()
+        .expect("Failed to get write lock on display state");
+
+    if display_state.cards() != *cards {
+      return
+    }
+
+    for (i, ui_card)
+        in display_state.cards_in_play_mut().iter_mut().enumerate() {
+      ui_card.set_visual_state(
+          if error_ord == Some(i) {
+            CardVisualState::Error
+          }
+          else {
+            CardVisualState::Normal
+          });
+    }
+  }
+
+  /// True if the current (or just released) press is short and still
+  /// enough to be a tap rather than a drag
+  fn is_tap(&self) -> bool {
+    match *self.gesture.lock().expect("Failed to lock gesture") {
+      Some(ref gesture) => gesture.is_tap(),
+      None => false
+    }
+  }
+
+  /// Like `is_tap`, but forgets the press so a single tap can't be acted
+  /// on twice
+  fn consume_tap(&self) -> bool {
+    self.gesture.lock().expect("Failed to lock gesture").take()
+        .map(|gesture| gesture.is_tap())
+        .unwrap_or(false)
+  }
+
+  /// Handle a tap on a supply card by adding the card to the end of the
+  /// cards in play
+  fn on_supply_card_tapped(&self, card: &Card) {
+    if !self.consume_tap() {
+      return
+    }
+
+    let mut display_state
+        = self.display_state.write().unwrap();
+
+    if let Err(violation)
+        = self.setup.rules().check_add(card, &display_state.cards()) {
+      info!("Not adding tapped card: {}", violation);
+      return
+    }
+
+    let ord = display_state.cards_in_play().len();
+    let ui_card = self.create_ui_card(card, Some(ord), false);
+
+    display_state.cards_in_play_mut().push(ui_card);
+
+    self.update_supply_cards(&mut *display_state);
+    self.trigger_evaluation(&display_state);
+    self.layout_play_area_cards(&mut *display_state, PLAY_CARD_MOVE_SECS);
+  }
+
+  /// Handle a key typed by the player.  Typed cards are inserted at the
+  /// typing cursor, backspace removes the card before the cursor and the
+  /// arrow keys move the cursor
+  fn on_key(&self, code: i64) {
+    let key = match Key::from_code(code) {
+      Some(key) => key,
+      None => return
+    };
+
+    let mut display_state = self.display_state.write()
+        .expect("Failed to get write lock on display state");
+
+    if display_state.card_in_flight().is_some() {
+      return
+    }
+
+    let cursor = display_state.cursor();
+
+    match key {
+      Key::Char(c) => {
+        match Card::from_key(c) {
+          Some(card) => self.insert_typed_card(&mut *display_state, card),
+          None => return
+        }
+      },
+      Key::Backspace if cursor > 0 => {
+        let removable = !display_state.cards_in_play()[cursor - 1]
+            .required_play_card();
+
+        if removable {
+          let ui_card = display_state.cards_in_play_mut().remove(cursor - 1);
+          self.discard_card(ui_card);
+        }
+
+        display_state.set_cursor(cursor - 1);
+      },
+      Key::Left if cursor > 0 => display_state.set_cursor(cursor - 1),
+      Key::Right => display_state.set_cursor(cursor + 1),
+      Key::Home => display_state.set_cursor(0),
+      Key::End => {
+        let end = display_state.cards_in_play().len();
+        display_state.set_cursor(end);
+      },
+      _ => return
+    }
+
+    self.update_supply_cards(&mut *display_state);
+    self.trigger_evaluation(&display_state);
+    self.layout_play_area_cards(&mut *display_state, PLAY_CARD_MOVE_SECS);
+  }
+
+  /// Insert the given typed card at the typing cursor if the game allows
+  /// it.  A typed number takes the next matching card in play from after
+  /// the cursor, since the cards before the cursor have already been
+  /// typed, or is added if a required card with that number is not in
+  /// play.  Other cards must be in the supply
+  fn insert_typed_card(&self,
+      display_state: &mut GameDisplayState<V::S>,
+      card: Card) {
+    let cursor = display_state.cursor();
+    let cards_in_play = display_state.cards();
+
+    let next_unplaced = match card {
+      Card::Number(_, _) => cards_in_play.iter()
+          .enumerate()
+          .skip(cursor)
+          .find(|&(_, c)| *c == card)
+          .map(|(i, _)| i),
+      _ => None
+    };
+
+    if let Some(i) = next_unplaced {
+      let ui_card = display_state.cards_in_play_mut().remove(i);
+
+      display_state.cards_in_play_mut().insert(cursor, ui_card);
+      display_state.set_cursor(cursor + 1);
+      return
+    }
+
+    let allowed = match card {
+      Card::Number(_, _) => {
+        let required = self.setup.required_cards().iter()
+            .filter(|c| **c == card)
+            .count();
+        let in_play = cards_in_play.iter().filter(|c| **c == card).count();
+
+        in_play < required
+      },
+      _ => {
+        self.setup.allowed_supply_cards().contains(&card)
+            && self.setup.rules().check_add(&card, &cards_in_play).is_ok()
+      }
+    };
+
+    if !allowed {
+      info!("Not inserting typed card {:?}", card);
+      return
+    }
+
+    let required_play_card = match card {
+      Card::Number(_, _) => *self.setup.rules().require_all_cards(),
+      _ => false
+    };
+
+    let ui_card = self.create_ui_card(&card, Some(cursor), required_play_card);
+
+    display_state.cards_in_play_mut().insert(cursor, ui_card);
+    display_state.set_cursor(cursor + 1);
+  }
+
   /// handle the initialization of a card being dragged.
   fn on_drag_start(&self, drag_point: &Point) {
 
+    *self.gesture.lock().expect("Failed to lock gesture") = Some(Gesture {
+      start: drag_point.clone(),
+      start_time: Instant::now(),
+      end_time: None,
+      max_distance: 0.
+    });
+
     let mut display_state
         = self.display_state.write().unwrap();
 
@@ -434,10 +1054,21 @@ impl <V,S> GamePresenter<V,S>
                 &mut *display_state))
           },
           None => {
-            Some(self.on_supply_card_drag_start(
+            match self.setup.rules().check_add(
                 &card_find_response.card,
-                &card_find_response.point_in_card,
-                &card_find_response.card_rect))
+                &display_state.cards()) {
+              Ok(()) => {
+                Some(self.on_supply_card_drag_start(
+                    &card_find_response.card,
+                    &card_find_response.point_in_card,
+                    &card_find_response.card_rect))
+              },
+              Err(violation) => {
+                info!("Not starting drag: {}", violation);
+                self.shake_supply_card(&card_find_response.card);
+                None
+              }
+            }
           }
         }
       },
@@ -445,6 +1076,7 @@ impl <V,S> GamePresenter<V,S>
     };
 
     display_state.set_card_in_flight(drag_state);
+    self.update_supply_cards(&mut *display_state);
   }
 
   /// Calculate the width that the given dragged card should be based on its
@@ -471,8 +1103,26 @@ impl <V,S> GamePresenter<V,S>
   /// handle the determination of where within the cards in play the card
   /// in flight will be placed
   fn on_drag_move(&self, drag_x: f64, drag_y: f64) {
-    let mut display_state
-        = self.display_state.write().unwrap();
+    if let Some(ref mut gesture)
+        = *self.gesture.lock().expect("Failed to lock gesture") {
+      gesture.max_distance = gesture.max_distance
+          .max(gesture.start.distance_to(&Point::new(drag_x, drag_y)));
+    }
+
+    let needs_preview = self.move_card_in_flight(drag_x, drag_y);
+
+    // Posted once the display state is released, since the preview's
+    // listener reads it and may run before `post` returns
+    if needs_preview {
+      self.event_bus.post(EvaluatePreview{});
+    }
+  }
+
+  /// Move the card in flight to follow the drag, and get whether a preview
+  /// evaluation needs to be posted because its place in play changed
+  fn move_card_in_flight(&self, drag_x: f64, drag_y: f64) -> bool {
+    let mut display_state = self.display_state.write()
+        .expect("Failed to get write lock on display state");
 
     let mut new_drag_ord_opt = None;
     let mut old_drag_ord_opt = None;
@@ -493,11 +1143,10 @@ impl <V,S> GamePresenter<V,S>
     }
 
     // Change in drag state order
-    if new_drag_ord_opt != old_drag_ord_opt {
-      self.on_dragged_card_ordinal_changed(
-          &mut *display_state,
-          &new_drag_ord_opt);
-    }
+    let needs_preview = new_drag_ord_opt != old_drag_ord_opt
+        && self.on_dragged_card_ordinal_changed(
+            &mut *display_state,
+            &new_drag_ord_opt);
 
     match display_state.card_in_flight_mut() {
       Some(drag_state) => {
@@ -506,16 +1155,19 @@ impl <V,S> GamePresenter<V,S>
       },
       _ => ()
     }
+
+    needs_preview
   }
 
   /// Handles the event where a card being dragged's ordinal in the play
   /// area changes. This will cause the played cards to shift around an
   /// opening that will (probably) be close to where the card is being
   /// dragged.  If the new drag ordinal not present, then the opening that
-  /// was present will close
+  /// was present will close.  Returns whether a preview evaluation needs to
+  /// be posted
   fn on_dragged_card_ordinal_changed(&self,
       display_state: &mut GameDisplayState<V::S>,
-      new_drag_ordinal: &Option<usize>) {
+      new_drag_ordinal: &Option<usize>) -> bool {
 
     match display_state.card_in_flight_mut().as_mut() {
       Some(drag_state) => {
@@ -524,20 +1176,375 @@ impl <V,S> GamePresenter<V,S>
       _ => ()
     };
 
-    self.layout_play_area_cards(display_state, 0.1);
+    self.update_supply_cards(display_state);
+    let needs_preview = self.trigger_preview_evaluation(display_state);
+    self.layout_play_area_cards(display_state, PLAY_CARD_MOVE_SECS);
+
+    needs_preview
+  }
+
+  /// Cancel any preview evaluation in progress, and queue a new one if the
+  /// card in flight would be dropped into play.  Returns whether the queued
+  /// preview needs an EvaluatePreview event, which must be posted after the
+  /// display state is released since the event's listener reads it
+  fn trigger_preview_evaluation(&self,
+      display_state: &GameDisplayState<V::S>) -> bool {
+    let preview_cards = display_state.preview_cards();
+    let has_preview = preview_cards.is_some();
+
+    {
+      let mut q_lock = self.preview_queue.lock()
+          .expect("Failed to lock preview queue");
+
+      *q_lock = preview_cards;
+      self.preview_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    if !has_preview {
+      self.show_tex(display_state.tex_area_rect());
+    }
+
+    has_preview
+  }
+
+  /// Cancel any preview evaluation in progress without starting a new one
+  fn cancel_preview_evaluation(&self) {
+    let mut q_lock = self.preview_queue.lock()
+        .expect("Failed to lock preview queue");
+
+    *q_lock = None;
+    self.preview_generation.fetch_add(1, Ordering::SeqCst);
+  }
+
+  /// Lay out the given tex and show it in the tex area.  Empty or
+  /// unrenderable tex clears the tex area
+  fn set_tex(&self, tex: &str) {
+    let layout = if tex.is_empty() {
+      TexLayout::default()
+    }
+    else {
+      TexLayout::new(tex).unwrap_or_else(|e| {
+        warn!("{}", e);
+        TexLayout::default()
+      })
+    };
+
+    {
+      *self.tex_layout.lock().expect("Failed to lock tex layout") = layout;
+    }
+
+    let display_state = self.display_state.read()
+        .expect("Failed to get read lock on display state");
+
+    self.show_tex(display_state.tex_area_rect());
+  }
+
+  /// Show the given tex faded in place of the current tex, or go back to
+  /// the current tex if there is no preview.  Nothing is drawn if the
+  /// preview evaluation of the given generation has since been cancelled
+  fn set_preview_tex(&self, tex: Option<&str>, generation: usize) {
+    let display_state = self.display_state.read()
+        .expect("Failed to get read lock on display state");
+    let tex_layout = self.tex_layout.lock()
+        .expect("Failed to lock tex layout");
+
+    // Whatever cancels a preview redraws the tex under the same lock, so
+    // checking here means a cancelled preview can't be drawn over it
+    if generation != self.preview_generation.load(Ordering::SeqCst) {
+      info!("Discarding cancelled preview evaluation");
+      return
+    }
+
+    let tex_area_rect = display_state.tex_area_rect();
+
+    match tex.map(TexLayout::new) {
+      Some(Ok(layout)) => {
+        self.draw_tex(&layout.fit_to(tex_area_rect), true);
+      },
+      _ => self.draw_tex(&tex_layout.fit_to(tex_area_rect), false)
+    }
+  }
+
+  /// Redraw the current tex layout fitted into the given rect.  The tex
+  /// layout stays locked while drawing so a preview can't be drawn over it
+  /// part way through
+  fn show_tex(&self, tex_area_rect: &Rect) {
+    let tex_layout = self.tex_layout.lock()
+        .expect("Failed to lock tex layout");
+
+    self.draw_tex(&tex_layout.fit_to(tex_area_rect), false);
+  }
+
+  /// Replace the tex shown in the view with the given (already fitted) tex
+  fn draw_tex(&self, fitted: &TexLayout, ghosted: bool) {
+    self.view.clear_tex();
+    self.view.set_tex_ghosted(ghosted);
+
+    for element in &fitted.elements {
+      match *element {
+        TexElement::Glyph { codepoint, left, baseline, size } => {
+          self.view.add_tex_glyph(codepoint as i64, left, baseline, size);
+        },
+        TexElement::Rule { left, top, width, height } => {
+          self.view.add_tex_rule(left, top, width, height);
+        }
+      }
+    }
+  }
+
+  /// Show the current value of the cards in play (if they have one) and
+  /// whether it solves the puzzle
+  fn update_hud(&self, value: Option<f64>) {
+    match value {
+      Some(v) => {
+        let solved = self.setup.is_solved_by(v);
+
+        self.view.get_value_text().set_text(format!("= {}", v));
+        self.view.get_status_text().set_text(
+            if solved {
+              SOLVED_STATUS.to_string()
+            }
+            else {
+              String::new()
+            });
+
+        if solved && !self.solved.swap(true, Ordering::SeqCst) {
+          self.celebrate();
+        }
+        else if !solved {
+          self.solved.store(false, Ordering::SeqCst);
+        }
+      },
+      None => {
+        self.view.get_value_text().set_text(String::new());
+        self.view.get_status_text().set_text(String::new());
+        self.solved.store(false, Ordering::SeqCst);
+      }
+    }
+  }
+
+  /// Start running the given animation, scheduling animation frames if they
+  /// aren't already running.  Frames come from the event bus's timer, so
+  /// this never waits on the display state and is safe to call while
+  /// holding it
+  fn start_animation(&self, animation: Animation<GameDisplayState<V::S>>)
+      -> AnimationId {
+    let id = self.animator.start(animation);
+
+    let needs_frame = {
+      let mut last_frame_time = self.last_frame_time.lock()
+          .expect("Failed to lock last frame time");
+
+      if last_frame_time.is_some() {
+        false
+      }
+      else {
+        *last_frame_time = Some(self.event_bus.now());
+        true
+      }
+    };
+
+    if needs_frame {
+      self.event_bus.post_after(*FRAME_INTERVAL, AnimationFrame{});
+    }
+
+    id
+  }
+
+  /// Slide the supply cards up into place from the bottom of the view one
+  /// after another
+  fn animate_supply_cards_in(&self, count: usize) {
+    for i in 0..count {
+      let tween = Tween::new(*SUPPLY_ENTER_DURATION, Easing::BackOut)
+          .delayed(*SUPPLY_ENTER_STAGGER * i as u32);
+
+      self.start_animation(Animation::new(tween, move |display_state, p| {
+        let height = display_state.size().height;
+
+        if let (Some(card), Some(rect)) = (
+            display_state.supply_cards().get(i),
+            display_state.get_supply_card_rect_by_index(&i)) {
+          card.set_rect(
+              &rect.offset(0., (1. - p) * (height - rect.top_left.y)));
+        }
+      }));
+    }
+  }
+
+  /// Shake the given supply card side to side to show it can't be played
+  fn shake_supply_card(&self, card: &Card) {
+    let card = card.clone();
+    let tween = Tween::new(*SHAKE_DURATION, Easing::Linear);
+
+    self.start_animation(Animation::new(tween, move |display_state, p| {
+      let ui_card_opt = display_state.supply_cards().iter()
+          .find(|ui_card| *ui_card.card() == card);
+
+      if let (Some(ui_card), Some(rect))
+          = (ui_card_opt, display_state.get_supply_card_rect_by_card(&card)) {
+        let dx = SHAKE_AMPLITUDE_FRAC * rect.size.width * (1. - p)
+            * (p * SHAKE_CYCLES * 2. * f64::consts::PI).sin();
+
+        ui_card.set_rect(&rect.offset(dx, 0.));
+      }
+    }));
+  }
+
+  /// Bounce each of the cards in play in turn to celebrate solving the
+  /// puzzle
+  fn celebrate(&self) {
+    self.cancel_celebration();
+
+    let count = self.display_state.read()
+        .expect("Failed to get read lock on display state")
+        .cards_in_play().len();
+
+    let ids : Vec<AnimationId> = (0..count)
+        .map(|i| {
+          let grow = Tween::new(*CELEBRATE_GROW_DURATION, Easing::EaseOut)
+              .delayed(*CELEBRATE_STAGGER * i as u32);
+          let settle
+              = Tween::new(*CELEBRATE_SETTLE_DURATION, Easing::BackOut);
+
+          self.start_animation(
+              Animation::new(grow, move |display_state, p| {
+                scale_play_card(display_state, i, 1. + CELEBRATE_SCALE * p);
+              })
+              .then(settle, move |display_state, p| {
+                scale_play_card(
+                    display_state, i, 1. + CELEBRATE_SCALE * (1. - p));
+              }))
+        })
+        .collect();
+
+    *self.celebration.lock().expect("Failed to lock celebration") = ids;
+  }
+
+  /// Stop any celebration in progress so it doesn't fight with cards being
+  /// moved around
+  fn cancel_celebration(&self) {
+    let ids : Vec<AnimationId> = self.celebration.lock()
+        .expect("Failed to lock celebration")
+        .drain(..)
+        .collect();
+
+    for id in ids {
+      self.animator.cancel(id);
+    }
+  }
+
+  /// Credit the player's campaign with whichever goal (if any) the given
+  /// value hits, and save the progress if a goal was reached or its
+  /// solution got shorter
+  fn credit_campaign(&self, value: f64, cards: &Vec<Card>) {
+    let credit = self.progress.write()
+        .expect("Failed to lock player progress for writing")
+        .credit(&self.setup, value, cards);
+
+    match credit {
+      Credit::Reached(goal) => info!("Reached campaign goal {}", goal),
+      Credit::Improved(goal) => info!("Found a shorter solution for {}", goal),
+      Credit::Unchanged => return
+    }
+
+    (self.progress_sink)();
   }
 
   /// Enqueue the string representation of the given game state in the
-  /// evaluation queue and post an eval event
+  /// evaluation queue and schedule an eval event if one isn't already
+  /// scheduled.
+  /// Changes made before the scheduled evaluation runs replace the queued
+  /// expression, so a burst of changes is only evaluated once
   fn trigger_evaluation(&self, display_state: &GameDisplayState<V::S>) {
-    {
+    let scheduled_time = {
       let mut q_lock = self.eval_queue.lock()
           .expect("Failed to lock event queue");
 
-      *q_lock = Some(display_state.to_string());
+      *q_lock = Some((display_state.to_string(), display_state.cards()));
+      self.eval_generation.fetch_add(1, Ordering::SeqCst);
+
+      let mut next_eval_time = self.next_eval_time.lock()
+          .expect("Failed to lock next eval time");
+
+      if next_eval_time.is_some() {
+        None
+      }
+      else {
+        let last_eval_time = *self.last_eval_time.lock()
+            .expect("Failed to lock last eval time");
+        *next_eval_time = Some(last_eval_time + *MIN_EVAL_SEPARATION);
+        *next_eval_time
+      }
+    };
+
+    // The event is delayed rather than waited for by its listener, so
+    // evaluations are at least MIN_EVAL_SEPARATION apart without holding up
+    // an event bus thread
+    if let Some(time) = scheduled_time {
+      let now = self.event_bus.now();
+      let delay = if time > now {
+        time - now
+      }
+      else {
+        Duration::from_millis(0)
+      };
+
+      self.event_bus.post_after(delay, Evaluate{});
+    }
+  }
+
+  /// Evaluate the given cards, reusing the last result if the expression
+  /// hasn't changed since it was computed
+  fn evaluate(&self, to_eval: &String, cards: &Vec<Card>) -> MathResult {
+    {
+      let last_eval = self.last_eval.lock().expect("Failed to lock last eval");
+
+      if let Some((ref last_str, ref last_resp)) = *last_eval {
+        if last_str == to_eval {
+          return Ok(last_resp.clone())
+        }
+      }
+    }
+
+    let result = self.math_engine.evaluate_cards(cards, &self.setup);
+
+    *self.last_eval_time.lock().expect("Failed to lock last eval time")
+        = self.event_bus.now();
+
+    if let Ok(ref resp) = result {
+      *self.last_eval.lock().expect("Failed to lock last eval")
+          = Some((to_eval.clone(), resp.clone()));
+    }
+
+    result
+  }
+
+  /// Called when a press on a card ends without moving far enough to be a
+  /// drag.  Taps on supply cards are handled by the card's click handler, and
+  /// tapping a play card takes it out of play
+  fn on_card_tapped(&self,
+      display_state: &mut GameDisplayState<V::S>,
+      drag_state: DraggedCardDisplayState<V::S>) {
+    if drag_state.orig_play_area_ord().is_some() {
+      self.on_card_dropped_not_in_play(display_state, drag_state);
+    }
+    else {
+      self.discard_card(drag_state.take_card());
     }
+  }
 
-    self.event_bus.post(Evaluate{});
+  /// Called when a dragged card is released
+  fn on_card_dropped(&self,
+      display_state: &mut GameDisplayState<V::S>,
+      mut drag_state: DraggedCardDisplayState<V::S>) {
+    match drag_state.play_area_ord_mut().take() {
+      Some(drag_ord) => {
+        self.on_card_dropped_into_play(display_state, drag_ord, drag_state);
+      }
+      None => {
+        self.on_card_dropped_not_in_play(display_state, drag_state);
+      }
+    }
   }
 
   /// Called when a card is released into play
@@ -549,53 +1556,61 @@ impl <V,S> GamePresenter<V,S>
     display_state.cards_in_play_mut().insert(drag_ord, ui_card);
   }
 
-  /// Called when a card is released but not released into play
+  /// Called when a card is released but not released into play.  Required
+  /// cards snap back to where they were taken from, and any other card is
+  /// discarded, the same as removing it with backspace
   fn on_card_dropped_not_in_play(&self,
       display_state: &mut GameDisplayState<V::S>,
       drag_state: DraggedCardDisplayState<V::S>) {
 
-    if drag_state.card().required_play_card().clone() {
-      let ord = drag_state.orig_play_area_ord().as_ref().cloned();
-      let card = drag_state.take_card();
-      display_state.cards_in_play_mut().insert(ord.unwrap(), card);
-    }
-    else {
-      if let Some(return_rect)
-          = display_state.get_supply_card_rect_by_card(
-              drag_state.card().card()) {
-        drag_state.card().set_rect_animated(&return_rect, 0.1)
-      }
+    let orig_ord = drag_state.orig_play_area_ord().as_ref().cloned();
+
+    match orig_ord {
+      Some(ord) if *drag_state.card().required_play_card() => {
+        let card = drag_state.take_card();
+        display_state.cards_in_play_mut().insert(ord, card);
+      },
+      _ => self.discard_card(drag_state.take_card())
     }
   }
 
+  /// Remove the given card from the game entirely.  The card must already
+  /// have been taken out of the cards in play
+  fn discard_card(&self, ui_card: UiCard<V::S>) {
+    info!("Discarding card {:?}", ui_card.card());
+    ui_card.remove_from_parent();
+  }
+
   /// Handles the positive release of a dragged supply card
   fn on_drag_end(&self) {
 
+    if let Some(ref mut gesture)
+        = *self.gesture.lock().expect("Failed to lock gesture") {
+      gesture.end_time = Some(Instant::now());
+    }
+
     let mut display_state
         = self.display_state.write().unwrap();
 
+    self.cancel_preview_evaluation();
+
+    let is_tap = self.is_tap();
+
     match display_state.card_in_flight_mut().take() {
-      Some(mut drag_state) => {
-        match drag_state.play_area_ord_mut().take() {
-          Some(drag_ord) => {
-            self.on_card_dropped_into_play(
-                &mut *display_state,
-                drag_ord,
-                drag_state);
-          }
-          None => {
-            self.on_card_dropped_not_in_play(
-                &mut *display_state,
-                drag_state);
-          }
+      Some(drag_state) => {
+        if is_tap {
+          self.on_card_tapped(&mut *display_state, drag_state);
+        }
+        else {
+          self.on_card_dropped(&mut *display_state, drag_state);
         }
-
       },
       _ => ()
     };
 
+    self.update_supply_cards(&mut *display_state);
     self.trigger_evaluation(&display_state);
-    self.layout_play_area_cards(&mut *display_state, 0.1);
+    self.layout_play_area_cards(&mut *display_state, PLAY_CARD_MOVE_SECS);
   }
 
   /// Initialize the display state with the initial game state
@@ -604,6 +1619,8 @@ impl <V,S> GamePresenter<V,S>
       game_state: GameState) {
 
     let mut new_display_state = GameDisplayState::default();
+    let require_all_cards
+        = *game_state.setup().rules().require_all_cards();
 
     *new_display_state.cards_in_play_mut() = game_state
         .cards_in_play()
@@ -612,17 +1629,34 @@ impl <V,S> GamePresenter<V,S>
         .map(|(i, card)| {
           this.create_ui_card(card,
           Some(i),
-          card.is_required_in_play())
+          require_all_cards && card.is_required_in_play())
         })
         .collect();
 
     *new_display_state.supply_cards_mut()
-        = game_state.setup().supply_cards().iter()
-            .map(|card| {
-              this.create_ui_card(card, None, true)
-            })
+        = game_state.setup().allowed_supply_cards().iter()
+            .map(|card| this.create_supply_ui_card(card))
             .collect();
 
+    this.update_supply_cards(&mut new_display_state);
+
+    for (i, ui_card) in new_display_state.supply_cards().iter().enumerate() {
+      let tapped_presenter = this.clone();
+      let card = ui_card.card().clone();
+
+      this.add_handler_registration(ui_card.add_click_handler(
+          InputRecorder::wrap_click_handler(
+              &this.recorder,
+              &format!("game.supply.{}", i),
+              create_click_handler!({
+                tapped_presenter.on_supply_card_tapped(&card);
+              }))));
+    }
+
+    this.view.get_goal_text().set_text(
+        format!("Goal: {}", game_state.setup().goal()));
+    this.update_hud(None);
+
     *this.display_state.write()
         .expect("Failed to get write lock on display state")
             = new_display_state;
@@ -632,48 +1666,97 @@ impl <V,S> GamePresenter<V,S>
     let copied_event_bus = self.event_bus.clone();
 
     self.add_handler_registration(Box::new(self.view
-        .add_layout_handler(create_layout_handler!(|w, h| {
-            copied_event_bus.post(Layout{width: w, height: h})
-        }))));
+        .add_layout_handler(InputRecorder::wrap_layout_handler(
+            &self.recorder,
+            "game.layout",
+            create_layout_handler!(|w, h| {
+                copied_event_bus.post(Layout{width: w, height: h})
+            })))));
+
+    let copied_event_bus = self.event_bus.clone();
+
+    self.add_handler_registration(Box::new(self.view
+        .get_pause_button()
+        .add_click_handler(InputRecorder::wrap_click_handler(
+            &self.recorder,
+            "game.pause",
+            create_click_handler!({
+                copied_event_bus.post(PauseGame{})
+            })))));
 
     let result = Arc::new(self);
+    let result_key = result.clone();
     let result_drag_start = result.clone();
     let result_drag_move = result.clone();
     let result_drag_end = result.clone();
 
     result.add_handler_registration(Box::new(result.view
-        .add_drag_handler(create_drag_handler!(
-              on_drag_start(wx, wy, _lx, _ly) {
-                result_drag_start.on_drag_start(&Point { x: wx, y: wy });
-              },
-              on_drag_move(wx, wy, _lx, _ly) {
-                result_drag_move.on_drag_move(wx, wy);
-              },
-              on_drag_end(_wx, _wy, _lx, _ly) {
-                result_drag_end.on_drag_end();
-              }
-          ))));
+        .add_drag_handler(InputRecorder::wrap_drag_handler(
+            &result.recorder,
+            "game.drag",
+            create_drag_handler!(
+                on_drag_start(wx, wy, _lx, _ly) {
+                  result_drag_start.on_drag_start(&Point { x: wx, y: wy });
+                },
+                on_drag_move(wx, wy, _lx, _ly) {
+                  result_drag_move.on_drag_move(wx, wy);
+                },
+                on_drag_end(_wx, _wy, _lx, _ly) {
+                  result_drag_end.on_drag_end();
+                }
+            )))));
+
+    result.add_handler_registration(Box::new(result.view
+        .add_key_handler(InputRecorder::wrap_key_handler(
+            &result.recorder,
+            "game.key",
+            create_key_handler!(|key| {
+                result_key.on_key(key);
+            })))));
+
+    result.add_listener_registration(
+        result.event_bus.register::<Layout, _>(&result));
 
     result.add_listener_registration(
-        result.event_bus.register_disambiguous(
-            FourFoursEvent::Layout,
-            &result,
-            Some(Layout{width: 0, height: 0})));
+        result.event_bus.register::<Evaluate, _>(&result));
 
     result.add_listener_registration(
-        result.event_bus.register_disambiguous(
-            FourFoursEvent::Evaluate,
-            &result,
-            Some(Evaluate{})));
+        result.event_bus.register::<EvaluatePreview, _>(&result));
+
+    result.add_listener_registration(
+        result.event_bus.register::<AnimationFrame, _>(&result));
+
+    result.add_listener_registration(
+        result.event_bus.register::<PauseGame, _>(&result));
+
+    result.add_listener_registration(
+        result.event_bus.register::<EnterBackground, _>(&result));
+
+    result.add_listener_registration(
+        result.event_bus.register::<EnterForeground, _>(&result));
+
+    result.add_listener_registration(
+        result.event_bus.register::<Terminate, _>(&result));
+
+    result.view.get_pause_button().set_text("Pause".to_string());
     result
   }
 
   pub fn new(
       view: V,
       event_bus: Arc<EventBus>,
-      runtime_resources: Arc<RuntimeResources<S>>)
+      runtime_resources: Arc<RuntimeResources<S>>,
+      game_state: GameState,
+      progress: Arc<RwLock<PlayerProgress>>,
+      progress_sink: Box<Fn()>,
+      game_state_sink: Box<Fn(GameState)>,
+      recorder: Arc<InputRecorder>)
           -> Arc<GamePresenter<V,S>> {
 
+    let created_time = event_bus.now();
+
+    recorder.set_setup(game_state.setup());
+
     let result = GamePresenter{
 
       view: view,
@@ -683,19 +1766,34 @@ impl <V,S> GamePresenter<V,S>
       handler_registrations: Mutex::new(Vec::new()),
 
       display_state: RwLock::new(GameDisplayState::default()),
+      gesture: Mutex::new(None),
       math_engine: MathEngine{},
+      tex_layout: Mutex::new(TexLayout::default()),
+
+      goal: AtomicIsize::new(game_state.setup().goal() as isize),
+      setup: game_state.setup().clone(),
 
-      goal: AtomicIsize::new(0),
+      progress: progress,
+      progress_sink: progress_sink,
+      game_state_sink: game_state_sink,
+      recorder: recorder,
 
       last_eval: Mutex::new(None),
       eval_queue: Mutex::new(None),
-      last_eval_time: Mutex::new(Instant::now()),
-      next_eval_time: Mutex::new(None)
+      eval_generation: AtomicUsize::new(0),
+      preview_queue: Mutex::new(None),
+      preview_generation: AtomicUsize::new(0),
+      last_eval_time: Mutex::new(created_time),
+      next_eval_time: Mutex::new(None),
+
+      animator: Animator::default(),
+      last_frame_time: Mutex::new(None),
+      supply_entered: AtomicBool::new(false),
+      solved: AtomicBool::new(false),
+      celebration: Mutex::new(Vec::new()),
+      suspended: AtomicBool::new(false)
     };
 
-    let game_state
-        = GameState::new(GameSetup::simple_new(1, vec![4, 4, 4, 4]));
-
     let arc_result = result.bind();
 
     GamePresenter::initialize_game_state(arc_result.clone(), game_state);
@@ -712,4 +1810,322 @@ impl <V,S> Drop for GamePresenter<V,S>
   fn drop(&mut self) {
     info!("Dropping Game Presenter")
   }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+use headless::{
+  HeadlessGameView,
+  HeadlessSprite,
+  HeadlessSystemView
+};
+
+#[cfg(test)]
+use replay::{Recording, ReplayPlayer};
+
+#[cfg(test)]
+use model::Rules;
+
+#[cfg(test)]
+use event::DispatchMode;
+
+#[cfg(test)]
+use ui::{HOME_CODE, RIGHT_ARROW_CODE};
+
+/// How far the event bus's clock is run forward for the evaluations and
+/// animations set off by a change to finish
+#[cfg(test)]
+const HEADLESS_SETTLE_SECS : u64 = 2;
+
+#[cfg(test)]
+type HeadlessGamePresenter = GamePresenter<HeadlessGameView, HeadlessSystemView>;
+
+/// Start a game of the given setup on a headless view that has been laid out
+/// at 800x600.  Events are handled inline so the game can be driven from the
+/// test's thread
+#[cfg(test)]
+fn start_headless_game(setup: GameSetup)
+    -> (HeadlessGameView, Arc<HeadlessGamePresenter>) {
+  start_recorded_headless_game(setup, Arc::new(InputRecorder::default()))
+}
+
+/// Start a game like `start_headless_game` with its input routed through the
+/// given recorder
+#[cfg(test)]
+fn start_recorded_headless_game(setup: GameSetup, recorder: Arc<InputRecorder>)
+    -> (HeadlessGameView, Arc<HeadlessGamePresenter>) {
+  let system_view = HeadlessSystemView::default();
+  let view = HeadlessGameView::default();
+
+  let presenter = GamePresenter::new(
+      view.clone(),
+      EventBus::with_mode(DispatchMode::Inline),
+      Arc::new(system_view.load_resources()),
+      GameState::new(setup),
+      Arc::new(RwLock::new(PlayerProgress::default())),
+      Box::new(|| {}),
+      Box::new(|_: GameState| {}),
+      recorder);
+
+  view.layout(800, 600);
+  settle(&presenter);
+
+  assert!(presenter.supply_entered.load(Ordering::SeqCst));
+  assert!(!presenter.animator.is_running());
+
+  (view, presenter)
+}
+
+/// Run the presenter's event bus clock forward until everything set off so
+/// far has finished
+#[cfg(test)]
+fn settle(presenter: &HeadlessGamePresenter) {
+  presenter.event_bus
+      .advance_clock(Duration::from_secs(HEADLESS_SETTLE_SECS));
+}
+
+/// Get the visible sprites showing the plus symbol, oldest first
+#[cfg(test)]
+fn plus_symbol_sprites(view: &HeadlessGameView) -> Vec<HeadlessSprite> {
+  let textures = HeadlessSystemView::default().load_resources()
+      .textures().get();
+  let plus = Some(textures.symbols().plus().clone());
+
+  view.visible_sprites().into_iter()
+      .filter(|sprite| sprite.state().texture == plus)
+      .collect()
+}
+
+#[test]
+fn test_layout_headless() {
+  let (view, _presenter) = start_headless_game(GameSetup::classic());
+  let sprites = view.visible_sprites();
+
+  assert!(!sprites.is_empty());
+  assert!(sprites.iter().all(|sprite| {
+    let rect = sprite.state().rect;
+
+    rect.size.width > 0.
+        && rect.top_left.x >= 0.
+        && rect.top_left.x + rect.size.width <= 800.
+        && rect.top_left.y >= 0.
+        && rect.top_left.y + rect.size.height <= 600.
+  }));
+
+  assert_eq!(view.get_goal_text().get_text(), "Goal: 1");
+  assert_eq!(plus_symbol_sprites(&view).len(), 1);
+}
+
+#[test]
+fn test_typing_solves_headless() {
+  let (view, presenter) = start_headless_game(GameSetup::classic());
+
+  view.key(HOME_CODE);
+  view.key(RIGHT_ARROW_CODE);
+  view.key(RIGHT_ARROW_CODE);
+  view.type_text("/");
+  settle(&presenter);
+
+  assert_eq!(view.get_status_text().get_text(), SOLVED_STATUS);
+  assert_eq!(view.get_value_text().get_text(), "= 1");
+  assert!(!view.tex().is_empty());
+}
+
+#[test]
+fn test_typing_takes_required_numbers_in_order_headless() {
+  let (view, presenter) = start_headless_game(GameSetup::classic());
+
+  view.key(HOME_CODE);
+  view.type_text("4+4/4-4");
+
+  assert_eq!(presenter.display_state.read().unwrap().cards(), vec![
+      required_number_card!(4),
+      Card::Plus,
+      required_number_card!(4),
+      Card::Divide,
+      required_number_card!(4),
+      Card::Minus,
+      required_number_card!(4)]);
+}
+
+#[test]
+fn test_burst_of_changes_evaluated_once_headless() {
+  let recorder = Arc::new(InputRecorder::default());
+  let (view, presenter)
+      = start_recorded_headless_game(GameSetup::classic(), recorder.clone());
+
+  recorder.start_recording();
+  view.key(HOME_CODE);
+  view.key(RIGHT_ARROW_CODE);
+  view.key(RIGHT_ARROW_CODE);
+  view.type_text("/");
+
+  assert!(recorder.stop_recording().unwrap().states().is_empty());
+
+  recorder.start_recording();
+  settle(&presenter);
+
+  assert_eq!(recorder.stop_recording().unwrap().states().len(), 1);
+  assert_eq!(view.get_status_text().get_text(), SOLVED_STATUS);
+}
+
+#[test]
+fn test_cancelled_preview_not_drawn_headless() {
+  let (view, presenter) = start_headless_game(GameSetup::classic());
+
+  let generation = presenter.preview_generation.load(Ordering::SeqCst);
+
+  presenter.cancel_preview_evaluation();
+  presenter.set_preview_tex(Some("4 = 4"), generation);
+
+  assert!(!view.is_tex_ghosted());
+
+  presenter.set_preview_tex(Some("4 = 4"),
+      presenter.preview_generation.load(Ordering::SeqCst));
+
+  assert!(view.is_tex_ghosted());
+}
+
+#[test]
+fn test_tapping_supply_card_headless() {
+  let (view, presenter) = start_headless_game(GameSetup::classic());
+
+  let supply_plus = plus_symbol_sprites(&view)[0].state().rect.center();
+
+  view.tap(supply_plus.x, supply_plus.y);
+
+  assert_eq!(plus_symbol_sprites(&view).len(), 2);
+
+  settle(&presenter);
+
+  assert_eq!(plus_symbol_sprites(&view)[1].state().tint,
+      CardVisualState::Error.tint());
+
+  assert_eq!(view.get_value_text().get_text(), "");
+}
+
+#[test]
+fn test_required_number_taken_out_of_play_snaps_back_headless() {
+  let (view, presenter) = start_headless_game(GameSetup::classic());
+
+  let first_number = presenter.display_state.read().unwrap()
+      .get_play_card_rect_by_index(&0).unwrap().center();
+
+  view.tap(first_number.x, first_number.y);
+
+  assert_eq!(presenter.display_state.read().unwrap().cards(),
+      vec![required_number_card!(4); 4]);
+}
+
+#[test]
+fn test_unrequired_number_dropped_out_of_play_discarded_headless() {
+  let mut rules = Rules::default();
+  rules.set_require_all_cards(false);
+
+  let setup = GameSetup::new_with_rules(
+      1,
+      vec![required_number_card!(4); 4],
+      all_non_number_cards!(),
+      rules);
+
+  let (view, presenter) = start_headless_game(setup);
+
+  let (first_number, away) = {
+    let display_state = presenter.display_state.read().unwrap();
+
+    (display_state.get_play_card_rect_by_index(&0).unwrap().center(),
+        display_state.tex_area_rect().center())
+  };
+
+  view.drag_start(first_number.x, first_number.y);
+  view.drag_move(away.x, away.y);
+  view.drag_end(away.x, away.y);
+
+  assert_eq!(presenter.display_state.read().unwrap().cards(),
+      vec![required_number_card!(4); 3]);
+}
+
+#[test]
+fn test_tap_only_adds_one_card_headless() {
+  let (view, _presenter) = start_headless_game(GameSetup::classic());
+
+  let supply_plus = plus_symbol_sprites(&view)[0].state().rect.center();
+
+  view.tap(supply_plus.x, supply_plus.y);
+  view.click(supply_plus.x, supply_plus.y);
+
+  assert_eq!(plus_symbol_sprites(&view).len(), 2);
+}
+
+#[test]
+fn test_required_card_dragged_out_highlighted_headless() {
+  let (view, presenter) = start_headless_game(GameSetup::classic());
+
+  let (first_number, away) = {
+    let display_state = presenter.display_state.read().unwrap();
+
+    (display_state.get_play_card_rect_by_index(&0).unwrap().center(),
+        display_state.tex_area_rect().center())
+  };
+
+  view.drag_start(first_number.x, first_number.y);
+  view.drag_move(away.x, away.y);
+
+  {
+    let display_state = presenter.display_state.read().unwrap();
+    let drag_state = display_state.card_in_flight().as_ref().unwrap();
+
+    assert_eq!(*drag_state.play_area_ord(), None);
+    assert_eq!(*drag_state.card().visual_state(),
+        CardVisualState::Highlighted);
+  }
+
+  view.drag_move(first_number.x, first_number.y);
+
+  assert_eq!(*presenter.display_state.read().unwrap()
+      .card_in_flight().as_ref().unwrap().card().visual_state(),
+      CardVisualState::Normal);
+
+  view.drag_end(first_number.x, first_number.y);
+
+  assert_eq!(presenter.display_state.read().unwrap().cards(),
+      vec![required_number_card!(4); 4]);
+}
+
+#[test]
+fn test_replay_reaches_recorded_state_headless() {
+  let recorder = Arc::new(InputRecorder::default());
+  let (view, presenter)
+      = start_recorded_headless_game(GameSetup::classic(), recorder.clone());
+
+  recorder.start_recording();
+  view.key(HOME_CODE);
+  view.key(RIGHT_ARROW_CODE);
+  view.key(RIGHT_ARROW_CODE);
+  view.type_text("/");
+  settle(&presenter);
+
+  assert_eq!(view.get_status_text().get_text(), SOLVED_STATUS);
+
+  let recording = Recording::from_replay_string(
+      &recorder.stop_recording().unwrap().to_replay_string()).unwrap();
+
+  assert_eq!(*recording.setup(), Some(GameSetup::classic()));
+  assert_eq!(*recording.size(), Some((800, 600)));
+
+  let replay_recorder = Arc::new(InputRecorder::default());
+  let (replay_view, replay_presenter) = start_recorded_headless_game(
+      GameSetup::classic(), replay_recorder.clone());
+
+  replay_recorder.start_recording();
+
+  assert_eq!(ReplayPlayer::new(replay_recorder.clone()).play(&recording),
+      Ok(4));
+
+  settle(&replay_presenter);
+
+  assert_eq!(replay_view.get_status_text().get_text(), SOLVED_STATUS);
+
+  let replayed = replay_recorder.stop_recording().unwrap();
+
+  assert_eq!(replayed.states().last(), recording.states().last());
+  assert_eq!(replay_view.get_value_text().get_text(), "= 1");
+}
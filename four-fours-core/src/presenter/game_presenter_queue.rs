@@ -0,0 +1,230 @@
+use std::sync::{Arc, Mutex, PoisonError, RwLock, Weak};
+
+use event::{EventBus, Layout};
+use model::GameState;
+use native::{RuntimeResources, SystemView};
+use presenter::{DeferredPresenter, GamePresenter};
+use ui::{GameView, HandlerRegistration, HasLayoutHandlers, LayoutHandler};
+
+/// A game view bound before the runtime resources finished loading, waiting
+/// for them so its presenter can be created
+struct PendingGameView<V,S>
+    where V: GameView,
+    S: SystemView {
+  view: V,
+  game_state: GameState,
+  presenter: Weak<DeferredPresenter<GamePresenter<V,S>>>,
+  /// Remembers the view's size while it waits, since the presenter will
+  /// miss the layout
+  layout_registration: Box<HandlerRegistration>,
+  last_layout: Arc<Mutex<Option<(i64, i64)>>>
+}
+
+/// Holds the runtime resources once they've loaded, and queues a game view
+/// bound before then until its presenter can be created
+pub struct GamePresenterQueue<V,S>
+    where V: GameView,
+    S: SystemView {
+  event_bus: Arc<EventBus>,
+  runtime_resources: RwLock<Option<Arc<RuntimeResources<S>>>>,
+  pending_game_view: Mutex<Option<PendingGameView<V,S>>>
+}
+
+impl <V,S> GamePresenterQueue<V,S>
+    where
+        V: GameView,
+        S: SystemView {
+
+  pub fn new(event_bus: Arc<EventBus>) -> GamePresenterQueue<V,S> {
+    GamePresenterQueue {
+      event_bus: event_bus,
+      runtime_resources: RwLock::new(None),
+      pending_game_view: Mutex::new(None)
+    }
+  }
+
+  /// Get the runtime resources, or an error if they haven't finished
+  /// loading
+  pub fn get_runtime_resources(&self)
+      -> Result<Arc<RuntimeResources<S>>, String> {
+    self.runtime_resources.read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+        .ok_or_else(|| "Runtime resources have not been loaded".to_string())
+  }
+
+  /// Create the presenter for the given game view with the given function.
+  /// If the runtime resources haven't finished loading, the view is queued
+  /// and its presenter is created once they have
+  pub fn bind<F>(&self, view: V, game_state: GameState, create_presenter: F)
+      -> Arc<DeferredPresenter<GamePresenter<V,S>>>
+      where F: FnOnce(V, Arc<RuntimeResources<S>>, GameState)
+          -> Arc<GamePresenter<V,S>> {
+    let mut pending_guard = self.pending_game_view.lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    // Resources are checked while holding the pending lock so they can't
+    // arrive between the check and queueing the view
+    match self.get_runtime_resources() {
+      Ok(runtime_resources) => {
+        DeferredPresenter::ready(
+            create_presenter(view, runtime_resources, game_state))
+      },
+      Err(e) => {
+        info!("Waiting to create the game presenter: {}", e);
+
+        let deferred = DeferredPresenter::pending();
+        let last_layout = Arc::new(Mutex::new(None));
+        let copied_last_layout = last_layout.clone();
+
+        let layout_registration = Box::new(view.add_layout_handler(
+            create_layout_handler!(|width, height| {
+              *copied_last_layout.lock()
+                  .unwrap_or_else(PoisonError::into_inner)
+                      = Some((width, height));
+            })));
+
+        if let Some(replaced) = pending_guard.take() {
+          warn!("Replacing a game view that was still waiting for resources");
+          replaced.layout_registration.deregister();
+        }
+
+        *pending_guard = Some(PendingGameView {
+          view: view,
+          game_state: game_state,
+          presenter: Arc::downgrade(&deferred),
+          layout_registration: layout_registration,
+          last_layout: last_layout
+        });
+
+        deferred
+      }
+    }
+  }
+
+  /// Store the loaded runtime resources and create the presenter for any
+  /// game view that was queued while they were loading.  If that view was
+  /// unbound before then, its game state is returned so it isn't lost
+  pub fn set_runtime_resources<F>(&self,
+      runtime_resources: RuntimeResources<S>,
+      create_presenter: F) -> Option<GameState>
+      where F: FnOnce(V, Arc<RuntimeResources<S>>, GameState)
+          -> Arc<GamePresenter<V,S>> {
+    let runtime_resources = Arc::new(runtime_resources);
+
+    *self.runtime_resources.write().unwrap_or_else(PoisonError::into_inner)
+        = Some(runtime_resources.clone());
+
+    let pending = self.pending_game_view.lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()?;
+
+    pending.layout_registration.deregister();
+
+    match pending.presenter.upgrade() {
+      Some(deferred) => {
+        info!("Creating the game presenter that was waiting for resources");
+
+        deferred.set(create_presenter(
+            pending.view, runtime_resources, pending.game_state));
+
+        let last_layout = *pending.last_layout.lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if let Some((width, height)) = last_layout {
+          self.event_bus.post(Layout{width: width, height: height});
+        }
+
+        None
+      },
+      None => {
+        info!("Game view was unbound before resources loaded");
+        Some(pending.game_state)
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+use std::time::Duration;
+
+#[cfg(test)]
+use event::DispatchMode;
+
+#[cfg(test)]
+use headless::{HeadlessGameView, HeadlessLoadingView, HeadlessSystemView};
+
+#[cfg(test)]
+use model::{GameSetup, PlayerProgress};
+
+#[cfg(test)]
+use presenter::LoadingPresenter;
+
+#[cfg(test)]
+use replay::InputRecorder;
+
+#[test]
+fn test_bind_before_resources_load_headless() {
+  let event_bus = EventBus::with_mode(DispatchMode::Inline);
+  let queue = Arc::new(
+      GamePresenterQueue::<HeadlessGameView, HeadlessSystemView>::new(
+          event_bus.clone()));
+  let view = HeadlessGameView::default();
+
+  let create_presenter = {
+    let event_bus = event_bus.clone();
+
+    move |view: HeadlessGameView,
+        runtime_resources: Arc<RuntimeResources<HeadlessSystemView>>,
+        game_state: GameState| {
+      GamePresenter::new(
+          view,
+          event_bus,
+          runtime_resources,
+          game_state,
+          Arc::new(RwLock::new(PlayerProgress::default())),
+          Box::new(|| {}),
+          Box::new(|_: GameState| {}),
+          Arc::new(InputRecorder::default()))
+    }
+  };
+
+  let deferred = queue.bind(
+      view.clone(),
+      GameState::new(GameSetup::classic()),
+      create_presenter.clone());
+
+  view.layout(800, 600);
+
+  assert!(!deferred.is_ready());
+  assert!(view.visible_sprites().is_empty());
+
+  // Resources are loaded by the LoadResources listener, which creates the
+  // queued presenter
+  let queue_copy = queue.clone();
+
+  let _loading_presenter = LoadingPresenter::new(
+      HeadlessLoadingView::default(),
+      Arc::new(HeadlessSystemView::default()),
+      event_bus.clone(),
+      Box::new(move |resources: RuntimeResources<HeadlessSystemView>| {
+        let unbound_game_state = queue_copy.set_runtime_resources(
+            resources, create_presenter.clone());
+
+        assert!(unbound_game_state.is_none());
+      }));
+
+  event_bus.advance_clock(Duration::from_secs(2));
+
+  assert!(deferred.is_ready());
+
+  let sprites = view.visible_sprites();
+
+  assert!(!sprites.is_empty());
+  assert!(sprites.iter().all(|sprite| {
+    let rect = sprite.state().rect;
+
+    rect.top_left.x + rect.size.width <= 800.
+        && rect.top_left.y + rect.size.height <= 600.
+  }));
+}
@@ -3,8 +3,7 @@ use event::{
     EventBus,
     LoadResources,
     ListenerRegistration,
-    EventListener,
-    FourFoursEvent
+    EventListener
 };
 
 use native::{
@@ -37,7 +36,7 @@ impl <V,S> EventListener<LoadResources>
   fn on_event(&self, _: &LoadResources) {
 
     let textures = Textures::new(
-        &self.system_view.get_texture_loader(),
+        Box::new(self.system_view.get_texture_loader()),
         & |p| {
           self.view.get_progress_indicator().set_int_value((p * 100.) as i64);
         });
@@ -64,7 +63,7 @@ impl <V,S> LoadingPresenter<V,S>
     let result = Arc::new(self);
 
     result.add_listener_registration(
-        result.event_bus.register(FourFoursEvent::LoadResources, &result));
+        result.event_bus.register(&result));
 
     result.view.get_progress_indicator().set_text(format!("Loading..."));
 
@@ -96,4 +95,30 @@ impl <V,S> Drop for LoadingPresenter<V,S>
   fn drop(&mut self) {
     info!("Dropping Loading Presenter")
   }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+use event::DispatchMode;
+
+#[cfg(test)]
+use headless::{HeadlessLoadingView, HeadlessSystemView};
+
+#[test]
+fn test_loads_resources_headless() {
+  let view = HeadlessLoadingView::default();
+  let loaded = Arc::new(Mutex::new(false));
+  let loaded_copy = loaded.clone();
+
+  let _presenter = LoadingPresenter::new(
+      view.clone(),
+      Arc::new(HeadlessSystemView::default()),
+      EventBus::with_mode(DispatchMode::Inline),
+      Box::new(move |_: RuntimeResources<HeadlessSystemView>| {
+        *loaded_copy.lock().unwrap() = true;
+      }));
+
+  assert_eq!(view.transitions(), vec![String::from("main_menu")]);
+
+  assert!(*loaded.lock().unwrap());
+  assert_eq!(view.get_progress_indicator().get_text(), "Loading...");
+  assert_eq!(view.get_progress_indicator().get_int_value(), 100);
+}
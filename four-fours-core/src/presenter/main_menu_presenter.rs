@@ -1,19 +1,38 @@
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 
 use ui::{ClickHandler, HasClickHandlers, HasText, MainMenuView, HandlerRegistration};
 
-use event::{ListenerRegistration, EventBus, EventListener, FourFoursEvent, StartGame};
+use event::{ListenerRegistration, EventBus, EventListener, StartGame};
 
+use model::{GameSetup, GameState, DailyChallenge, PlayerProgress};
+
+use replay::InputRecorder;
 
 pub struct MainMenuPresenter<V : MainMenuView> {
   view: V,
+  progress: Arc<RwLock<PlayerProgress>>,
+  game_state_sink: Box<Fn(GameState)>,
   handler_registrations: Mutex<Vec<Box<HandlerRegistration>>>,
   listener_registrations: Mutex<Vec<ListenerRegistration>>,
-  event_bus: Arc<EventBus>
+  event_bus: Arc<EventBus>,
+  recorder: Arc<InputRecorder>
 }
 
 impl <V: MainMenuView> EventListener<StartGame> for MainMenuPresenter<V> {
-  fn on_event(&self, _: &StartGame) {
+  fn on_event(&self, event: &StartGame) {
+    let game_state = if event.daily {
+      let challenge = DailyChallenge::today();
+      info!("Starting daily challenge for {:?}", challenge.date());
+      GameState::new(challenge.setup().clone())
+    }
+    else {
+      self.progress.read()
+          .expect("Failed to lock player progress for reading")
+          .next_campaign_game(&GameSetup::classic())
+    };
+
+    (self.game_state_sink)(game_state);
+
     self.view.transition_to_game_view()
   }
 }
@@ -37,28 +56,52 @@ impl <V: MainMenuView> MainMenuPresenter<V> {
 
     self.add_handler_registration(Box::new(self.view
         .get_start_new_game_button()
-        .add_click_handler(create_click_handler!({
-            copied_event_bus.post(StartGame{new: true})
-        }))));
+        .add_click_handler(InputRecorder::wrap_click_handler(
+            &self.recorder,
+            "main_menu.new_game",
+            create_click_handler!({
+                copied_event_bus.post(StartGame{new: true, daily: false})
+            })))));
+
+    let copied_event_bus = self.event_bus.clone();
+
+    self.add_handler_registration(Box::new(self.view
+        .get_start_daily_challenge_button()
+        .add_click_handler(InputRecorder::wrap_click_handler(
+            &self.recorder,
+            "main_menu.daily",
+            create_click_handler!({
+                copied_event_bus.post(StartGame{new: true, daily: true})
+            })))));
 
     let result = Arc::new(self);
 
     result.add_listener_registration(
-        result.event_bus.register(FourFoursEvent::StartGame, &result));
+        result.event_bus.register(&result));
 
     result.view.get_start_new_game_button().set_text(
         "New Game".to_string());
 
+    result.view.get_start_daily_challenge_button().set_text(
+        "Daily Challenge".to_string());
+
     result
   }
 
-  pub fn new(view: V, event_bus: Arc<EventBus>)
-      -> Arc<MainMenuPresenter<V>> {
+  pub fn new(view: V,
+      event_bus: Arc<EventBus>,
+      progress: Arc<RwLock<PlayerProgress>>,
+      game_state_sink: Box<Fn(GameState)>,
+      recorder: Arc<InputRecorder>)
+          -> Arc<MainMenuPresenter<V>> {
     let result = MainMenuPresenter {
       view: view,
+      progress: progress,
+      game_state_sink: game_state_sink,
       handler_registrations: Mutex::new(Vec::new()),
       listener_registrations: Mutex::new(Vec::new()),
       event_bus: event_bus,
+      recorder: recorder
     };
 
     result.bind()
@@ -1,7 +1,13 @@
 pub use self::loading_presenter::LoadingPresenter;
 pub use self::main_menu_presenter::MainMenuPresenter;
 pub use self::game_presenter::GamePresenter;
+pub use self::pause_presenter::PausePresenter;
+pub use self::deferred_presenter::DeferredPresenter;
+pub use self::game_presenter_queue::GamePresenterQueue;
 
 mod loading_presenter;
 mod main_menu_presenter;
 mod game_presenter;
+mod pause_presenter;
+mod deferred_presenter;
+mod game_presenter_queue;
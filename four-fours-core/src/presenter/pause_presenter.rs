@@ -0,0 +1,194 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use ui::{ClickHandler, HasClickHandlers, HasText, PauseView, HandlerRegistration};
+
+use event::{
+  ListenerRegistration,
+  EventBus,
+  EventListener,
+  ResumeGame,
+  RestartGame,
+  NextPuzzle,
+  QuitToMainMenu
+};
+
+use model::{GameState, NavigationStack, PlayerProgress, Screen};
+
+use replay::InputRecorder;
+
+pub struct PausePresenter<V : PauseView> {
+  view: V,
+  game_state: Option<GameState>,
+  progress: Arc<RwLock<PlayerProgress>>,
+  navigation: Arc<RwLock<NavigationStack>>,
+  game_state_sink: Box<Fn(Option<GameState>)>,
+  handler_registrations: Mutex<Vec<Box<HandlerRegistration>>>,
+  listener_registrations: Mutex<Vec<ListenerRegistration>>,
+  event_bus: Arc<EventBus>,
+  recorder: Arc<InputRecorder>
+}
+
+impl <V: PauseView> EventListener<ResumeGame> for PausePresenter<V> {
+  fn on_event(&self, _: &ResumeGame) {
+    let previous = self.navigation.read()
+        .expect("Failed to lock navigation for reading")
+        .previous();
+
+    match (previous, self.game_state.is_some()) {
+      (Some(Screen::Game), true) => self.view.transition_to_game_view(),
+      _ => self.view.transition_to_main_menu_view()
+    }
+  }
+}
+
+impl <V: PauseView> EventListener<RestartGame> for PausePresenter<V> {
+  fn on_event(&self, _: &RestartGame) {
+    match self.game_state {
+      Some(ref game_state) => {
+        (self.game_state_sink)(
+            Some(GameState::new(game_state.setup().clone())));
+        self.view.transition_to_game_view();
+      },
+      None => self.view.transition_to_main_menu_view()
+    }
+  }
+}
+
+impl <V: PauseView> EventListener<NextPuzzle> for PausePresenter<V> {
+  fn on_event(&self, _: &NextPuzzle) {
+    match self.game_state {
+      Some(ref game_state) => {
+        let next_game = self.progress.read()
+            .expect("Failed to lock player progress for reading")
+            .next_campaign_game(game_state.setup());
+
+        (self.game_state_sink)(Some(next_game));
+        self.view.transition_to_game_view();
+      },
+      None => self.view.transition_to_main_menu_view()
+    }
+  }
+}
+
+impl <V: PauseView> EventListener<QuitToMainMenu> for PausePresenter<V> {
+  fn on_event(&self, _: &QuitToMainMenu) {
+    (self.game_state_sink)(None);
+    self.view.transition_to_main_menu_view();
+  }
+}
+
+impl <V: PauseView> PausePresenter<V> {
+
+  fn add_listener_registration(&self, lr: ListenerRegistration) {
+    if let Ok(mut locked_list) = self.listener_registrations.lock() {
+      locked_list.push(lr);
+    }
+  }
+
+  fn add_handler_registration(&self, hr: Box<HandlerRegistration>) {
+    if let Ok(mut locked_list) = self.handler_registrations.lock() {
+      locked_list.push(hr);
+    }
+  }
+
+  fn bind(self) -> Arc<PausePresenter<V>> {
+    let copied_event_bus = self.event_bus.clone();
+
+    self.add_handler_registration(Box::new(self.view
+        .get_resume_button()
+        .add_click_handler(InputRecorder::wrap_click_handler(
+            &self.recorder,
+            "pause.resume",
+            create_click_handler!({
+                copied_event_bus.post(ResumeGame{})
+            })))));
+
+    let copied_event_bus = self.event_bus.clone();
+
+    self.add_handler_registration(Box::new(self.view
+        .get_restart_button()
+        .add_click_handler(InputRecorder::wrap_click_handler(
+            &self.recorder,
+            "pause.restart",
+            create_click_handler!({
+                copied_event_bus.post(RestartGame{})
+            })))));
+
+    let copied_event_bus = self.event_bus.clone();
+
+    self.add_handler_registration(Box::new(self.view
+        .get_next_puzzle_button()
+        .add_click_handler(InputRecorder::wrap_click_handler(
+            &self.recorder,
+            "pause.next_puzzle",
+            create_click_handler!({
+                copied_event_bus.post(NextPuzzle{})
+            })))));
+
+    let copied_event_bus = self.event_bus.clone();
+
+    self.add_handler_registration(Box::new(self.view
+        .get_main_menu_button()
+        .add_click_handler(InputRecorder::wrap_click_handler(
+            &self.recorder,
+            "pause.main_menu",
+            create_click_handler!({
+                copied_event_bus.post(QuitToMainMenu{})
+            })))));
+
+    let result = Arc::new(self);
+
+    result.add_listener_registration(
+        result.event_bus.register::<ResumeGame, _>(&result));
+
+    result.add_listener_registration(
+        result.event_bus.register::<RestartGame, _>(&result));
+
+    result.add_listener_registration(
+        result.event_bus.register::<NextPuzzle, _>(&result));
+
+    result.add_listener_registration(
+        result.event_bus.register::<QuitToMainMenu, _>(&result));
+
+    result.view.get_resume_button().set_text("Resume".to_string());
+    result.view.get_restart_button().set_text("Restart".to_string());
+    result.view.get_next_puzzle_button().set_text("Next Puzzle".to_string());
+    result.view.get_main_menu_button().set_text("Main Menu".to_string());
+
+    result
+  }
+
+  /// Create a pause presenter for the given paused game (if there is one).
+  /// The game state sink sets (or clears) the game the game view will show
+  /// next
+  pub fn new(view: V,
+      event_bus: Arc<EventBus>,
+      game_state: Option<GameState>,
+      progress: Arc<RwLock<PlayerProgress>>,
+      navigation: Arc<RwLock<NavigationStack>>,
+      game_state_sink: Box<Fn(Option<GameState>)>,
+      recorder: Arc<InputRecorder>)
+          -> Arc<PausePresenter<V>> {
+    let result = PausePresenter {
+      view: view,
+      game_state: game_state,
+      progress: progress,
+      navigation: navigation,
+      game_state_sink: game_state_sink,
+      handler_registrations: Mutex::new(Vec::new()),
+      listener_registrations: Mutex::new(Vec::new()),
+      event_bus: event_bus,
+      recorder: recorder
+    };
+
+    result.bind()
+  }
+
+}
+
+
+impl <V: PauseView> Drop for PausePresenter<V> {
+  fn drop(&mut self) {
+    info!("Dropping Pause Presenter")
+  }
+}
\ No newline at end of file
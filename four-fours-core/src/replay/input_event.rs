@@ -0,0 +1,102 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// A single input sent to one of the handlers presenters register with
+/// their views.  Drag coordinates are global x and y followed by local x
+/// and y, the same as the drag handler arguments
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputEvent {
+  Layout(i64, i64),
+  DragStart(f64, f64, f64, f64),
+  DragMove(f64, f64, f64, f64),
+  DragEnd(f64, f64, f64, f64),
+  Click,
+  Key(i64)
+}
+
+impl Display for InputEvent {
+  fn fmt(&self, format: &mut Formatter) -> fmt::Result {
+    use self::InputEvent::*;
+    match *self {
+      Layout(width, height) => write!(format, "layout {} {}", width, height),
+      DragStart(gx, gy, lx, ly) => {
+        write!(format, "drag_start {} {} {} {}", gx, gy, lx, ly)
+      },
+      DragMove(gx, gy, lx, ly) => {
+        write!(format, "drag_move {} {} {} {}", gx, gy, lx, ly)
+      },
+      DragEnd(gx, gy, lx, ly) => {
+        write!(format, "drag_end {} {} {} {}", gx, gy, lx, ly)
+      },
+      Click => write!(format, "click"),
+      Key(code) => write!(format, "key {}", code)
+    }
+  }
+}
+
+impl InputEvent {
+
+  /// Parse an input event written with `to_string`
+  pub fn parse(value: &str) -> Result<InputEvent, String> {
+    let mut parts = value.split_whitespace();
+    let name = parts.next().unwrap_or("");
+    let args : Vec<&str> = parts.collect();
+
+    let float_args = || -> Result<Vec<f64>, String> {
+      args.iter()
+          .map(|arg| arg.parse::<f64>()
+              .map_err(|_| format!("Invalid number: {}", value)))
+          .collect()
+    };
+
+    let int_args = || -> Result<Vec<i64>, String> {
+      args.iter()
+          .map(|arg| arg.parse::<i64>()
+              .map_err(|_| format!("Invalid number: {}", value)))
+          .collect()
+    };
+
+    let result = match (name, args.len()) {
+      ("layout", 2) => {
+        let a = int_args()?;
+        InputEvent::Layout(a[0], a[1])
+      },
+      ("drag_start", 4) => {
+        let a = float_args()?;
+        InputEvent::DragStart(a[0], a[1], a[2], a[3])
+      },
+      ("drag_move", 4) => {
+        let a = float_args()?;
+        InputEvent::DragMove(a[0], a[1], a[2], a[3])
+      },
+      ("drag_end", 4) => {
+        let a = float_args()?;
+        InputEvent::DragEnd(a[0], a[1], a[2], a[3])
+      },
+      ("click", 0) => InputEvent::Click,
+      ("key", 1) => InputEvent::Key(int_args()?[0]),
+      _ => return Err(format!("Unrecognized input: {}", value))
+    };
+
+    Ok(result)
+  }
+}
+
+#[test]
+fn test_round_trip() {
+  let events = vec![
+    InputEvent::Layout(800, 600),
+    InputEvent::DragStart(1.5, 2., 0., -3.25),
+    InputEvent::DragMove(10., 20., 0., 0.),
+    InputEvent::DragEnd(10., 20., 0., 0.),
+    InputEvent::Click,
+    InputEvent::Key(0xF702)
+  ];
+
+  for event in events {
+    assert_eq!(InputEvent::parse(&event.to_string()), Ok(event));
+  }
+
+  assert!(InputEvent::parse("layout 1").is_err());
+  assert!(InputEvent::parse("key x").is_err());
+}
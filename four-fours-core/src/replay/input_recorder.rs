@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Instant;
+
+use model::GameSetup;
+use replay::{InputEvent, Recording, ReplayEvent};
+use ui::{ClickHandler, DragHandler, KeyHandler, LayoutHandler};
+
+/// A handler registered with the recorder, held weakly so the recorder
+/// doesn't keep views' handlers alive
+#[derive(Clone)]
+enum Route {
+  Layout(Weak<LayoutHandler>),
+  Drag(Weak<DragHandler>),
+  Click(Weak<ClickHandler>),
+  Key(Weak<KeyHandler>)
+}
+
+/// Sits between views and the handlers presenters give them.  Every handler
+/// is registered under a target name so inputs can be recorded by target
+/// and later sent back to whichever handler is registered under the same
+/// target
+#[derive(Default)]
+pub struct InputRecorder {
+  routes: Mutex<HashMap<String, Route>>,
+  recording: Mutex<Option<(Instant, Recording)>>,
+  /// The game in play and the size of the last layout, kept so recordings
+  /// can start with them
+  setup: Mutex<Option<GameSetup>>,
+  size: Mutex<Option<(i64, i64)>>
+}
+
+impl InputRecorder {
+
+  /// Start a new recording, discarding any recording in progress
+  pub fn start_recording(&self) {
+    let recording = Recording::new(
+        self.setup.lock().expect("Failed to lock setup").clone(),
+        *self.size.lock().expect("Failed to lock size"));
+
+    *self.recording.lock().expect("Failed to lock recording")
+        = Some((Instant::now(), recording));
+  }
+
+  /// Note the setup of the game now in play, for the header of recordings
+  /// started from now on
+  pub fn set_setup(&self, setup: &GameSetup) {
+    *self.setup.lock().expect("Failed to lock setup") = Some(setup.clone());
+  }
+
+  /// Stop recording and get what was recorded, if a recording was started
+  pub fn stop_recording(&self) -> Option<Recording> {
+    self.recording.lock().expect("Failed to lock recording").take()
+        .map(|(_, recording)| recording)
+  }
+
+  pub fn is_recording(&self) -> bool {
+    self.recording.lock().expect("Failed to lock recording").is_some()
+  }
+
+  pub fn record_input(&self, target: &str, input: &InputEvent) {
+    self.record(ReplayEvent::Input(target.to_string(), input.clone()));
+  }
+
+  /// Record a description of the game state after it changed
+  pub fn record_state(&self, state: String) {
+    self.record(ReplayEvent::State(state));
+  }
+
+  fn record(&self, event: ReplayEvent) {
+    if let Some((start, ref mut recording))
+        = *self.recording.lock().expect("Failed to lock recording") {
+      recording.push(start.elapsed(), event);
+    }
+  }
+
+  /// True if a live handler is registered under the given target
+  pub fn has_route(&self, target: &str) -> bool {
+    let route = self.routes.lock().expect("Failed to lock routes")
+        .get(target).cloned();
+
+    match route {
+      Some(Route::Layout(ref handler)) => handler.upgrade().is_some(),
+      Some(Route::Drag(ref handler)) => handler.upgrade().is_some(),
+      Some(Route::Click(ref handler)) => handler.upgrade().is_some(),
+      Some(Route::Key(ref handler)) => handler.upgrade().is_some(),
+      None => false
+    }
+  }
+
+  /// Send the given input to the handler registered under the given target
+  /// as though it came from the view.  The input is recorded if a recording
+  /// is in progress and the handler accepts it
+  pub fn dispatch(&self, target: &str, input: &InputEvent)
+      -> Result<(), String> {
+    let route = self.routes.lock().expect("Failed to lock routes")
+        .get(target).cloned()
+        .ok_or_else(|| format!("Nothing registered for {}", target))?;
+
+    let missing = || format!("Handler for {} has been dropped", target);
+    let mismatch = || format!("{} can't handle {}", target, input);
+    let record = || self.record_input(target, input);
+
+    match route {
+      Route::Layout(handler) => {
+        let handler = handler.upgrade().ok_or_else(missing)?;
+        match *input {
+          InputEvent::Layout(width, height) => {
+            record();
+            handler.on_layout(width, height)
+          },
+          _ => return Err(mismatch())
+        }
+      },
+      Route::Drag(handler) => {
+        let handler = handler.upgrade().ok_or_else(missing)?;
+        match *input {
+          InputEvent::DragStart(gx, gy, lx, ly) => {
+            record();
+            handler.on_drag_start(gx, gy, lx, ly)
+          },
+          InputEvent::DragMove(gx, gy, lx, ly) => {
+            record();
+            handler.on_drag_move(gx, gy, lx, ly)
+          },
+          InputEvent::DragEnd(gx, gy, lx, ly) => {
+            record();
+            handler.on_drag_end(gx, gy, lx, ly)
+          },
+          _ => return Err(mismatch())
+        }
+      },
+      Route::Click(handler) => {
+        let handler = handler.upgrade().ok_or_else(missing)?;
+        match *input {
+          InputEvent::Click => {
+            record();
+            handler.on_click()
+          },
+          _ => return Err(mismatch())
+        }
+      },
+      Route::Key(handler) => {
+        let handler = handler.upgrade().ok_or_else(missing)?;
+        match *input {
+          InputEvent::Key(code) => {
+            record();
+            handler.on_key(code)
+          },
+          _ => return Err(mismatch())
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  fn add_route(&self, target: &str, route: Route) {
+    self.routes.lock().expect("Failed to lock routes")
+        .insert(target.to_string(), route);
+  }
+
+  /// Register the given handler under the given target and get a handler
+  /// for the view that records layouts before passing them on
+  pub fn wrap_layout_handler(this: &Arc<InputRecorder>,
+      target: &str,
+      handler: LayoutHandler) -> LayoutHandler {
+    let handler = Arc::new(handler);
+    let recorder = this.clone();
+    let target = target.to_string();
+
+    this.add_route(&target, Route::Layout(Arc::downgrade(&handler)));
+
+    create_layout_handler!(|width, height| {
+      *recorder.size.lock().expect("Failed to lock size")
+          = Some((width, height));
+      recorder.record_input(&target, &InputEvent::Layout(width, height));
+      handler.on_layout(width, height);
+    })
+  }
+
+  /// Register the given handler under the given target and get a handler
+  /// for the view that records drags before passing them on
+  pub fn wrap_drag_handler(this: &Arc<InputRecorder>,
+      target: &str,
+      handler: DragHandler) -> DragHandler {
+    let handler = Arc::new(handler);
+
+    this.add_route(target, Route::Drag(Arc::downgrade(&handler)));
+
+    let (start_recorder, start_target, start_handler)
+        = (this.clone(), target.to_string(), handler.clone());
+    let (move_recorder, move_target, move_handler)
+        = (this.clone(), target.to_string(), handler.clone());
+    let (end_recorder, end_target, end_handler)
+        = (this.clone(), target.to_string(), handler);
+
+    create_drag_handler!(
+        on_drag_start(gx, gy, lx, ly) {
+          start_recorder.record_input(&start_target,
+              &InputEvent::DragStart(gx, gy, lx, ly));
+          start_handler.on_drag_start(gx, gy, lx, ly);
+        },
+        on_drag_move(gx, gy, lx, ly) {
+          move_recorder.record_input(&move_target,
+              &InputEvent::DragMove(gx, gy, lx, ly));
+          move_handler.on_drag_move(gx, gy, lx, ly);
+        },
+        on_drag_end(gx, gy, lx, ly) {
+          end_recorder.record_input(&end_target,
+              &InputEvent::DragEnd(gx, gy, lx, ly));
+          end_handler.on_drag_end(gx, gy, lx, ly);
+        }
+    )
+  }
+
+  /// Register the given handler under the given target and get a handler
+  /// for the view that records clicks before passing them on
+  pub fn wrap_click_handler(this: &Arc<InputRecorder>,
+      target: &str,
+      handler: ClickHandler) -> ClickHandler {
+    let handler = Arc::new(handler);
+    let recorder = this.clone();
+    let target = target.to_string();
+
+    this.add_route(&target, Route::Click(Arc::downgrade(&handler)));
+
+    create_click_handler!({
+      recorder.record_input(&target, &InputEvent::Click);
+      handler.on_click();
+    })
+  }
+
+  /// Register the given handler under the given target and get a handler
+  /// for the view that records keys before passing them on
+  pub fn wrap_key_handler(this: &Arc<InputRecorder>,
+      target: &str,
+      handler: KeyHandler) -> KeyHandler {
+    let handler = Arc::new(handler);
+    let recorder = this.clone();
+    let target = target.to_string();
+
+    this.add_route(&target, Route::Key(Arc::downgrade(&handler)));
+
+    create_key_handler!(|key| {
+      recorder.record_input(&target, &InputEvent::Key(key));
+      handler.on_key(key);
+    })
+  }
+}
+
+#[test]
+fn test_record_and_dispatch() {
+  let recorder = Arc::new(InputRecorder::default());
+  let keys = Arc::new(Mutex::new(Vec::new()));
+  let keys_copy = keys.clone();
+
+  let view_handler = InputRecorder::wrap_key_handler(&recorder, "game.key",
+      create_key_handler!(|key| { keys_copy.lock().unwrap().push(key); }));
+
+  view_handler.on_key(1);
+  recorder.start_recording();
+  view_handler.on_key(2);
+  recorder.dispatch("game.key", &InputEvent::Key(3)).unwrap();
+  recorder.record_state(String::from("done"));
+
+  assert!(recorder.dispatch("game.key", &InputEvent::Click).is_err());
+  assert!(recorder.dispatch("game.drag", &InputEvent::Key(4)).is_err());
+
+  let recording = recorder.stop_recording().unwrap();
+  let events : Vec<ReplayEvent> = recording.entries().iter()
+      .map(|entry| entry.event.clone())
+      .collect();
+
+  assert_eq!(*keys.lock().unwrap(), vec![1, 2, 3]);
+  assert_eq!(events, vec![
+    ReplayEvent::Input(String::from("game.key"), InputEvent::Key(2)),
+    ReplayEvent::Input(String::from("game.key"), InputEvent::Key(3)),
+    ReplayEvent::State(String::from("done"))
+  ]);
+
+  drop(view_handler);
+
+  assert!(!recorder.has_route("game.key"));
+}
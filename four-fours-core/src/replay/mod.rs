@@ -0,0 +1,9 @@
+pub use self::input_event::InputEvent;
+pub use self::recording::{Recording, ReplayEntry, ReplayEvent};
+pub use self::input_recorder::InputRecorder;
+pub use self::replay_player::ReplayPlayer;
+
+mod input_event;
+mod recording;
+mod input_recorder;
+mod replay_player;
@@ -0,0 +1,183 @@
+use std::fs;
+use std::io;
+use std::time::Duration;
+
+use model::GameSetup;
+use replay::InputEvent;
+
+const SETUP_PREFIX : &str = "setup";
+const SIZE_PREFIX : &str = "size";
+
+/// Something that happened during a recorded session
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayEvent {
+
+  /// An input sent to the handler registered under the given target
+  Input(String, InputEvent),
+
+  /// A description of the game state after it changed
+  State(String)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayEntry {
+
+  /// Time since the recording started
+  pub at: Duration,
+  pub event: ReplayEvent
+}
+
+/// A timestamped stream of inputs and the game state changes they caused.
+/// Recordings are saved as text with a header noting what the game looked
+/// like when recording started, followed by one entry per line
+#[derive(Debug, Clone, Default, PartialEq, Getters)]
+pub struct Recording {
+
+  /// Setup of the game in play when recording started, if there was one
+  #[get = "pub"] setup: Option<GameSetup>,
+
+  /// Size the views were laid out at when recording started, if known
+  #[get = "pub"] size: Option<(i64, i64)>,
+
+  #[get = "pub"] entries: Vec<ReplayEntry>
+}
+
+impl Recording {
+
+  /// Start an empty recording with the given header
+  pub fn new(setup: Option<GameSetup>, size: Option<(i64, i64)>)
+      -> Recording {
+    Recording {
+      setup: setup,
+      size: size,
+      entries: Vec::default()
+    }
+  }
+
+  pub fn push(&mut self, at: Duration, event: ReplayEvent) {
+    self.entries.push(ReplayEntry { at: at, event: event });
+  }
+
+  /// Get the descriptions of the game state changes, oldest first
+  pub fn states(&self) -> Vec<String> {
+    self.entries.iter()
+        .filter_map(|entry| {
+          match entry.event {
+            ReplayEvent::State(ref state) => Some(state.clone()),
+            _ => None
+          }
+        })
+        .collect()
+  }
+
+  pub fn to_replay_string(&self) -> String {
+    let mut lines : Vec<String> = Vec::new();
+
+    if let Some(ref setup) = self.setup {
+      lines.push(format!("{} {}", SETUP_PREFIX, setup.to_save_string()));
+    }
+
+    if let Some((width, height)) = self.size {
+      lines.push(format!("{} {} {}", SIZE_PREFIX, width, height));
+    }
+
+    lines.extend(self.entries.iter()
+        .map(|entry| {
+          let millis = entry.at.as_secs() * 1000
+              + u64::from(entry.at.subsec_millis());
+
+          match entry.event {
+            ReplayEvent::Input(ref target, ref input) => {
+              format!("{} input {} {}", millis, target, input)
+            },
+            ReplayEvent::State(ref state) => {
+              format!("{} state {}", millis, state)
+            }
+          }
+        }));
+
+    lines.join("\n")
+  }
+
+  pub fn from_replay_string(saved: &str) -> Result<Recording, String> {
+    let mut result = Recording::default();
+
+    for line in saved.lines().filter(|line| !line.trim().is_empty()) {
+      let mut parts = line.splitn(3, ' ');
+      let first = parts.next().unwrap_or("");
+
+      if first == SETUP_PREFIX {
+        result.setup = Some(GameSetup::from_save_string(
+            &line[SETUP_PREFIX.len()..])?);
+        continue
+      }
+
+      if first == SIZE_PREFIX {
+        let width = parts.next().and_then(|w| w.parse::<i64>().ok());
+        let height = parts.next().and_then(|h| h.parse::<i64>().ok());
+
+        match (width, height) {
+          (Some(width), Some(height)) => result.size = Some((width, height)),
+          _ => return Err(format!("Invalid size: {}", line))
+        }
+
+        continue
+      }
+
+      let millis = first.parse::<u64>()
+          .map_err(|_| format!("Invalid time: {}", line))?;
+      let at = Duration::from_millis(millis);
+
+      match (parts.next(), parts.next()) {
+        (Some("input"), Some(rest)) => {
+          let mut input_parts = rest.splitn(2, ' ');
+          let target = input_parts.next().unwrap_or("");
+          let input = InputEvent::parse(input_parts.next().unwrap_or(""))?;
+
+          result.push(at, ReplayEvent::Input(target.to_string(), input));
+        },
+        (Some("state"), rest) => {
+          result.push(at,
+              ReplayEvent::State(rest.unwrap_or("").to_string()));
+        },
+        _ => return Err(format!("Unrecognized replay line: {}", line))
+      }
+    }
+
+    Ok(result)
+  }
+
+  pub fn save(&self, path: &str) -> io::Result<()> {
+    fs::write(path, self.to_replay_string())
+  }
+
+  pub fn load(path: &str) -> Result<Recording, String> {
+    let saved = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    Recording::from_replay_string(&saved)
+  }
+}
+
+#[test]
+fn test_save_round_trip() {
+  let mut recording
+      = Recording::new(Some(GameSetup::classic()), Some((800, 600)));
+
+  recording.push(Duration::from_millis(0),
+      ReplayEvent::Input(String::from("game.layout"),
+          InputEvent::Layout(800, 600)));
+  recording.push(Duration::from_millis(1250),
+      ReplayEvent::Input(String::from("supply.√"), InputEvent::Click));
+  recording.push(Duration::from_millis(1800),
+      ReplayEvent::State(String::from("4444√ = error")));
+
+  let saved = recording.to_replay_string();
+
+  assert!(saved.starts_with("setup goal=1 "));
+  assert_eq!(saved.lines().nth(1), Some("size 800 600"));
+  assert_eq!(saved.lines().nth(3), Some("1250 input supply.√ click"));
+  assert_eq!(Recording::from_replay_string(&saved), Ok(recording.clone()));
+  assert_eq!(recording.states(), vec![String::from("4444√ = error")]);
+  assert!(Recording::from_replay_string("12 jump").is_err());
+}
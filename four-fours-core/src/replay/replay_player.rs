@@ -0,0 +1,87 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use animation::to_secs;
+use replay::{InputRecorder, Recording, ReplayEvent};
+
+/// How often the player checks whether an input's target has been
+/// registered yet
+const ROUTE_POLL_INTERVAL_MS : u64 = 5;
+
+/// Sends the inputs of a recording back through an input recorder.  Inputs
+/// are sent in their recorded order, and each one waits for a handler to be
+/// registered under its target, so inputs aimed at a view that hasn't been
+/// bound yet (after a transition for example) wait for it
+pub struct ReplayPlayer {
+  recorder: Arc<InputRecorder>,
+
+  /// How fast to play compared to the recording, or none to send each
+  /// input as soon as it can be handled
+  speed: Option<f64>,
+  route_timeout: Duration
+}
+
+impl ReplayPlayer {
+
+  pub fn new(recorder: Arc<InputRecorder>) -> ReplayPlayer {
+    ReplayPlayer {
+      recorder: recorder,
+      speed: None,
+      route_timeout: Duration::from_secs(5)
+    }
+  }
+
+  /// Play back with the recorded timing scaled by the given speed
+  pub fn with_speed(mut self, speed: f64) -> ReplayPlayer {
+    self.speed = Some(speed);
+    self
+  }
+
+  /// Set how long to wait for a handler to be registered under an input's
+  /// target before giving up
+  pub fn with_route_timeout(mut self, route_timeout: Duration)
+      -> ReplayPlayer {
+    self.route_timeout = route_timeout;
+    self
+  }
+
+  /// Send all the inputs of the given recording.  Blocks until every input
+  /// has been sent, and returns how many were sent
+  pub fn play(&self, recording: &Recording) -> Result<usize, String> {
+    let start = Instant::now();
+    let mut sent = 0;
+
+    for entry in recording.entries() {
+      if let ReplayEvent::Input(ref target, ref input) = entry.event {
+        if let Some(speed) = self.speed {
+          let wait = to_secs(entry.at) / speed - to_secs(start.elapsed());
+
+          if wait > 0. {
+            thread::sleep(Duration::from_millis((wait * 1000.) as u64));
+          }
+        }
+
+        self.wait_for_route(target)?;
+        self.recorder.dispatch(target, input)?;
+        sent += 1;
+      }
+    }
+
+    Ok(sent)
+  }
+
+  fn wait_for_route(&self, target: &str) -> Result<(), String> {
+    let start = Instant::now();
+
+    while !self.recorder.has_route(target) {
+      if start.elapsed() > self.route_timeout {
+        return Err(format!("Timed out waiting for {}", target))
+      }
+
+      thread::sleep(Duration::from_millis(ROUTE_POLL_INTERVAL_MS));
+    }
+
+    Ok(())
+  }
+}
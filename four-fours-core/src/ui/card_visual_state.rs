@@ -0,0 +1,42 @@
+
+/// How a card is drawn to draw the player's attention to it (or away from
+/// it)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CardVisualState {
+  Normal,
+
+  /// A card the player should use
+  Highlighted,
+
+  /// A card where the expression stops making sense
+  Error,
+
+  /// A card that can't be played right now
+  Dimmed
+}
+
+impl CardVisualState {
+
+  /// Get the tint color and blend factor cards in this state are drawn with
+  pub fn tint(&self) -> (f64, f64, f64, f64) {
+    match *self {
+      CardVisualState::Normal => (1., 1., 1., 0.),
+      CardVisualState::Highlighted => (1., 0.8, 0.2, 0.35),
+      CardVisualState::Error => (0.9, 0.1, 0.1, 0.5),
+      CardVisualState::Dimmed => (0.5, 0.5, 0.5, 0.3)
+    }
+  }
+
+  pub fn alpha(&self) -> f64 {
+    match *self {
+      CardVisualState::Dimmed => 0.5,
+      _ => 1.
+    }
+  }
+}
+
+impl Default for CardVisualState {
+  fn default() -> CardVisualState {
+    CardVisualState::Normal
+  }
+}
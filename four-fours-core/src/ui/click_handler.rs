@@ -20,6 +20,6 @@ impl ClickHandler {
 
 impl Drop for ClickHandler {
   fn drop(&mut self) {
-    println!("Dropping Click Handler")
+    debug!("Dropping Click Handler")
   }
 }
\ No newline at end of file
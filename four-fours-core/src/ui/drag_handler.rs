@@ -59,6 +59,6 @@ impl DragHandler {
 
 impl Drop for DragHandler {
   fn drop(&mut self) {
-    println!("Dropping Drag Handler")
+    debug!("Dropping Drag Handler")
   }
 }
@@ -1,7 +1,10 @@
-
 use ui::{
+  Button,
   HasDragHandlers,
+  HasKeyHandlers,
   HasLayoutHandlers,
+  HasTexDisplay,
+  HasText,
   SpriteSource
 };
 
@@ -9,6 +12,17 @@ pub trait GameView
     : SpriteSource
     + HasLayoutHandlers
     + HasDragHandlers
+    + HasKeyHandlers
+    + HasTexDisplay
     + 'static {
+  type TA : HasText;
+  type B : Button;
+
+  fn get_goal_text(&self) -> Self::TA;
+  fn get_value_text(&self) -> Self::TA;
+  fn get_status_text(&self) -> Self::TA;
+
+  fn get_pause_button(&self) -> Self::B;
 
+  fn transition_to_pause_view(&self);
 }
\ No newline at end of file
@@ -0,0 +1,7 @@
+use ui::{ HandlerRegistration, KeyHandler };
+
+pub trait HasKeyHandlers : 'static {
+  type R : HandlerRegistration;
+
+  fn add_key_handler(&self, handler: KeyHandler) -> Self::R;
+}
\ No newline at end of file
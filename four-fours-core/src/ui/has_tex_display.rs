@@ -0,0 +1,13 @@
+
+/// Views that can draw a formula laid out in core.  Glyphs are positioned by
+/// the left edge of their baseline, and rules (fraction bars, radical tops)
+/// by their top left corner
+pub trait HasTexDisplay {
+  fn clear_tex(&self);
+  fn add_tex_glyph(&self, codepoint: i64, left: f64, baseline: f64, size: f64);
+  fn add_tex_rule(&self, left: f64, top: f64, width: f64, height: f64);
+
+  /// Show the formula faded to mark it as a preview rather than the value of
+  /// the cards in play
+  fn set_tex_ghosted(&self, ghosted: bool);
+}
@@ -0,0 +1,68 @@
+macro_rules! create_key_handler {
+   (| $key:ident | $body:block) => {
+    KeyHandler::new(Box::new(move |$key| $body ))
+  };
+}
+
+pub const BACKSPACE_CODE : i64 = 0x08;
+pub const DELETE_CODE : i64 = 0x7F;
+pub const LEFT_ARROW_CODE : i64 = 0xF702;
+pub const RIGHT_ARROW_CODE : i64 = 0xF703;
+pub const HOME_CODE : i64 = 0xF729;
+pub const END_CODE : i64 = 0xF72B;
+
+/// A key pressed by the player.  Native code reports keys as unicode code
+/// points, with the navigation keys using the private-use code points AppKit
+/// assigns them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Key {
+  Char(char),
+  Backspace,
+  Left,
+  Right,
+  Home,
+  End
+}
+
+impl Key {
+  pub fn from_code(code: i64) -> Option<Key> {
+    match code {
+      BACKSPACE_CODE | DELETE_CODE => Some(Key::Backspace),
+      LEFT_ARROW_CODE => Some(Key::Left),
+      RIGHT_ARROW_CODE => Some(Key::Right),
+      HOME_CODE => Some(Key::Home),
+      END_CODE => Some(Key::End),
+      _ if code >= 0 && code <= ::std::u32::MAX as i64 => {
+        ::std::char::from_u32(code as u32).map(Key::Char)
+      },
+      _ => None
+    }
+  }
+}
+
+pub struct KeyHandler(Box<Fn(i64) + 'static>);
+
+impl KeyHandler {
+  pub fn new(_self: Box<Fn(i64) + 'static>) -> KeyHandler {
+    KeyHandler(_self)
+  }
+
+  pub fn on_key(&self, key: i64) {
+    (self.0)(key)
+  }
+}
+
+
+impl Drop for KeyHandler {
+  fn drop(&mut self) {
+    debug!("Dropping Key Handler")
+  }
+}
+
+#[test]
+fn test_from_code() {
+  assert_eq!(Key::from_code('4' as i64), Some(Key::Char('4')));
+  assert_eq!(Key::from_code(DELETE_CODE), Some(Key::Backspace));
+  assert_eq!(Key::from_code(LEFT_ARROW_CODE), Some(Key::Left));
+  assert_eq!(Key::from_code(-1), None);
+}
\ No newline at end of file
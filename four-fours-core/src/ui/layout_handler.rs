@@ -20,6 +20,6 @@ impl LayoutHandler {
 
 impl Drop for LayoutHandler {
   fn drop(&mut self) {
-    println!("Dropping Layout Handler")
+    debug!("Dropping Layout Handler")
   }
 }
\ No newline at end of file
@@ -6,6 +6,8 @@ pub trait MainMenuView : 'static + Sized {
 
   fn get_start_new_game_button(&self) -> Self::B;
 
+  fn get_start_daily_challenge_button(&self) -> Self::B;
+
   fn transition_to_game_view(&self);
 
 }
\ No newline at end of file
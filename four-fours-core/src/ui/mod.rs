@@ -15,13 +15,27 @@ pub use self::sprite::Sprite;
 pub use self::has_layout_handlers::HasLayoutHandlers;
 pub use self::layout_handler::LayoutHandler;
 pub use self::ui_card::UiCard;
+pub use self::card_visual_state::CardVisualState;
 pub use self::sprite_source::SpriteSource;
 pub use self::drag_handler::DragHandler;
 pub use self::has_drag_handlers::HasDragHandlers;
+pub use self::key_handler::{
+  Key,
+  KeyHandler,
+  BACKSPACE_CODE,
+  DELETE_CODE,
+  LEFT_ARROW_CODE,
+  RIGHT_ARROW_CODE,
+  HOME_CODE,
+  END_CODE
+};
+pub use self::has_key_handlers::HasKeyHandlers;
+pub use self::has_tex_display::HasTexDisplay;
 
 pub use self::loading_view::LoadingView;
 pub use self::main_menu_view::MainMenuView;
 pub use self::game_view::GameView;
+pub use self::pause_view::PauseView;
 
 mod button;
 mod has_click_handlers;
@@ -37,8 +51,11 @@ mod progress_bar;
 mod sprite;
 mod has_layout_handlers;
 mod ui_card;
+mod card_visual_state;
 mod sprite_source;
 mod has_drag_handlers;
+mod has_key_handlers;
+mod has_tex_display;
 
 #[macro_use]
 mod click_handler;
@@ -49,6 +66,10 @@ mod layout_handler;
 #[macro_use]
 mod drag_handler;
 
+#[macro_use]
+mod key_handler;
+
 mod loading_view;
 mod main_menu_view;
-mod game_view;
\ No newline at end of file
+mod game_view;
+mod pause_view;
\ No newline at end of file
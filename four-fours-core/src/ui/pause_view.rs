@@ -0,0 +1,19 @@
+
+use ui::Button;
+
+pub trait PauseView : 'static + Sized {
+  type B : Button;
+
+  fn get_resume_button(&self) -> Self::B;
+
+  fn get_restart_button(&self) -> Self::B;
+
+  fn get_next_puzzle_button(&self) -> Self::B;
+
+  fn get_main_menu_button(&self) -> Self::B;
+
+  fn transition_to_game_view(&self);
+
+  fn transition_to_main_menu_view(&self);
+
+}
\ No newline at end of file
@@ -4,6 +4,7 @@ use ui::{
   HasMutableSize,
   HasMutableLocation,
   HasMutableVisibility,
+  HasClickHandlers,
   HasDragHandlers
 };
 
@@ -11,12 +12,19 @@ pub trait Sprite
     : HasMutableSize
     + HasMutableLocation
     + HasMutableVisibility
+    + HasClickHandlers
     + HasDragHandlers
     + 'static {
   type T : Texture;
 
   fn set_texture(&self, texture: &Self::T);
 
+  /// Blend the sprite's texture toward the given color.  A blend factor of 0
+  /// shows the texture unchanged
+  fn set_tint(&self, red: f64, green: f64, blue: f64, blend_factor: f64);
+
+  fn set_alpha(&self, alpha: f64);
+
   fn propagate_events_to(&self, &Self);
 
   fn remove_from_parent(&self);
@@ -1,15 +1,21 @@
 use model::{
   Rect,
   Card,
+  MAX_SUPPLY_LIMIT
 };
-use native::{ Texture, Textures };
+use native::{ Texture, TextureSet };
 use ui::{
+  CardVisualState,
+  ClickHandler,
+  HandlerRegistration,
+  HasClickHandlers,
   Sprite,
   SpriteSource,
   HasMutableVisibility
 };
 
 const SYMBOL_WIDTH_FRAC : f64 = 0.6;
+const COUNT_WIDTH_FRAC : f64 = 0.3;
 
 #[derive(Getters,Setters)]
 pub struct UiCard<S>
@@ -19,10 +25,16 @@ pub struct UiCard<S>
   symbol_sprite: S,
   symbol_texture_aspect_ratio: f64,
 
+  /// Sprite showing how many more of this card can be played.  Only present
+  /// on supply cards with a limited supply
+  count_sprite: Option<S>,
+
   #[get = "pub"] card: Card,
 
   #[get = "pub"] required_play_card: bool,
-  #[get = "pub"] #[set = "pub"] play_area_ord: Option<usize>
+  #[get = "pub"] #[set = "pub"] play_area_ord: Option<usize>,
+
+  #[get = "pub"] visual_state: CardVisualState
 }
 
 impl <T,S> UiCard<S>
@@ -34,7 +46,7 @@ impl <T,S> UiCard<S>
       card: Card,
       play_area_ord: Option<usize>,
       required_play_card: bool,
-      textures: &Textures<T>,
+      textures: &TextureSet<T>,
       sprite_source: &SpriteSource<T = T, S = S>)
           -> UiCard<S> {
 
@@ -45,19 +57,8 @@ impl <T,S> UiCard<S>
 
     let symbol_texture = match card {
       Card::Number(val, _) => {
-        match val {
-          0 => textures.symbols().zero(),
-          1 => textures.symbols().one(),
-          2 => textures.symbols().two(),
-          3 => textures.symbols().three(),
-          4 => textures.symbols().four(),
-          5 => textures.symbols().five(),
-          6 => textures.symbols().six(),
-          7 => textures.symbols().seven(),
-          8 => textures.symbols().eight(),
-          9 => textures.symbols().nine(),
-          _ => panic!("Invalid number {}", val)
-        }
+        textures.symbols().number(val)
+            .unwrap_or_else(|| panic!("Invalid number {}", val))
       },
       Card::Decimal => textures.symbols().decimal(),
       Card::Plus => textures.symbols().plus(),
@@ -80,9 +81,67 @@ impl <T,S> UiCard<S>
       required_play_card: required_play_card,
       card_sprite: card_sprite,
       symbol_sprite: symbol_sprite,
-      symbol_texture_aspect_ratio: symbol_texture.get_aspect_ratio()
+      symbol_texture_aspect_ratio: symbol_texture.get_aspect_ratio(),
+      count_sprite: None,
+      visual_state: CardVisualState::Normal
+    }
+
+  }
+
+  /// Create a card for the supply area.  If the supply of the card is
+  /// limited, the card will show how many more can be played
+  pub fn new_supply_card(
+      card: Card,
+      supply_limit: Option<usize>,
+      textures: &TextureSet<T>,
+      sprite_source: &SpriteSource<T = T, S = S>)
+          -> UiCard<S> {
+    let mut result = UiCard::new(card, None, true, textures, sprite_source);
+
+    if let Some(limit) = supply_limit {
+      result.count_sprite = Some(sprite_source.create_sprite());
+      result.set_remaining_count(limit, textures);
+    }
+
+    result
+  }
+
+  /// Add a handler for taps on this card
+  pub fn add_click_handler(&self, handler: ClickHandler)
+      -> Box<HandlerRegistration> {
+    Box::new(self.card_sprite.add_click_handler(handler))
+  }
+
+  /// Show the given number of cards remaining in the supply.  This does
+  /// nothing for cards without a limited supply.  Only one digit is shown,
+  /// which is why limits can't be above `MAX_SUPPLY_LIMIT`
+  pub fn set_remaining_count(&self,
+      remaining: usize,
+      textures: &TextureSet<T>) {
+    if let Some(ref count_sprite) = self.count_sprite {
+      let digit = remaining.min(MAX_SUPPLY_LIMIT) as i64;
+
+      if let Some(texture) = textures.symbols().number(digit) {
+        count_sprite.set_texture(texture);
+      }
+    }
+  }
+
+  /// Change how this card is drawn.  Setting the state the card is already
+  /// in does nothing
+  pub fn set_visual_state(&mut self, state: CardVisualState) {
+    if self.visual_state == state {
+      return
     }
 
+    let (red, green, blue, blend_factor) = state.tint();
+
+    for sprite in self.sprites() {
+      sprite.set_tint(red, green, blue, blend_factor);
+      sprite.set_alpha(state.alpha());
+    }
+
+    self.visual_state = state;
   }
 
   /// Set the location and size of this card immediately.  This will set the
@@ -120,6 +179,41 @@ impl <T,S> UiCard<S>
         sym_left,
         sym_top,
         duration_seconds);
+
+    if let Some(ref count_sprite) = self.count_sprite {
+      let count_size = width * COUNT_WIDTH_FRAC;
+
+      count_sprite.set_size_animated(count_size, count_size, duration_seconds);
+      count_sprite.set_location_animated(
+          left + width - count_size,
+          top,
+          duration_seconds);
+    }
+  }
+}
+
+impl <S> UiCard<S> where S : Sprite {
+
+  /// Get all the sprites this card is drawn with
+  fn sprites(&self) -> Vec<&S> {
+    let mut result = vec![&self.card_sprite, &self.symbol_sprite];
+
+    if let Some(ref count_sprite) = self.count_sprite {
+      result.push(count_sprite);
+    }
+
+    result
+  }
+
+  /// Remove all the sprites of this card from the view.  The card should not
+  /// be used after this
+  pub fn remove_from_parent(&self) {
+    self.card_sprite.remove_from_parent();
+    self.symbol_sprite.remove_from_parent();
+
+    if let Some(ref count_sprite) = self.count_sprite {
+      count_sprite.remove_from_parent();
+    }
   }
 }
 
@@ -128,6 +222,10 @@ impl <S> HasMutableVisibility for UiCard<S> where S : Sprite {
   fn set_visible(&self, visible: bool) {
     self.card_sprite.set_visible(visible);
     self.symbol_sprite.set_visible(visible);
+
+    if let Some(ref count_sprite) = self.count_sprite {
+      count_sprite.set_visible(visible);
+    }
   }
 
 }
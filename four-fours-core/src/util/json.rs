@@ -0,0 +1,31 @@
+use std::fmt::Write;
+
+/// Quote the given text as a JSON string
+pub fn json_string(text: &str) -> String {
+  let mut result = String::with_capacity(text.len() + 2);
+
+  result.push('"');
+
+  for c in text.chars() {
+    match c {
+      '"' => result.push_str("\\\""),
+      '\\' => result.push_str("\\\\"),
+      '\n' => result.push_str("\\n"),
+      '\r' => result.push_str("\\r"),
+      '\t' => result.push_str("\\t"),
+      c if (c as u32) < 0x20 => {
+        let _ = write!(result, "\\u{:04x}", c as u32);
+      },
+      c => result.push(c)
+    }
+  }
+
+  result.push('"');
+  result
+}
+
+#[test]
+fn test_json_string() {
+  assert_eq!(json_string("Layout { width: 8 }"), "\"Layout { width: 8 }\"");
+  assert_eq!(json_string("a \"b\"\\\n\u{1}"), "\"a \\\"b\\\"\\\\\\n\\u0001\"");
+}
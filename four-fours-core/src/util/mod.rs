@@ -1,3 +1,9 @@
 pub use self::rust_string::RustString;
+pub use self::seeded_random::SeededRandom;
+pub use self::json::json_string;
+pub use self::panic_message::panic_message;
 
-mod rust_string;
\ No newline at end of file
+mod rust_string;
+mod seeded_random;
+mod json;
+mod panic_message;
@@ -0,0 +1,21 @@
+use std::any::Any;
+
+/// Get the message a panic was started with, for reporting it
+pub fn panic_message(cause: &(Any + Send)) -> String {
+  if let Some(message) = cause.downcast_ref::<&str>() {
+    message.to_string()
+  }
+  else if let Some(message) = cause.downcast_ref::<String>() {
+    message.clone()
+  }
+  else {
+    "unknown cause".to_string()
+  }
+}
+
+#[test]
+fn test_panic_message() {
+  let cause = ::std::panic::catch_unwind(|| panic!("Bad {}", 4)).unwrap_err();
+
+  assert_eq!(panic_message(&*cause), "Bad 4");
+}
@@ -0,0 +1,75 @@
+
+/// Small deterministic pseudo-random generator (splitmix64).  The sequence
+/// produced for a given seed is the same on every platform, which is what
+/// lets things like the daily challenge agree across devices without a
+/// network
+#[derive(Debug, Clone)]
+pub struct SeededRandom {
+  state: u64
+}
+
+impl SeededRandom {
+
+  pub fn new(seed: u64) -> SeededRandom {
+    SeededRandom { state: seed }
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+  }
+
+  /// Get a value in the range [low, high).  Panics if the range is empty
+  pub fn next_in_range(&mut self, low: i64, high: i64) -> i64 {
+    if high <= low {
+      panic!("Cannot pick a random value from an empty range");
+    }
+
+    let span = (high - low) as u64;
+
+    low + (self.next_u64() % span) as i64
+  }
+
+  /// Returns true with (approximately) the given probability
+  pub fn next_bool(&mut self, probability: f64) -> bool {
+    ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+  }
+
+  /// Shuffle the given values in place (Fisher-Yates)
+  pub fn shuffle<T>(&mut self, values: &mut Vec<T>) {
+    let len = values.len();
+
+    for i in (1..len).rev() {
+      let j = self.next_in_range(0, (i + 1) as i64) as usize;
+      values.swap(i, j);
+    }
+  }
+}
+
+#[test]
+fn test_same_seed_same_sequence() {
+  let mut first = SeededRandom::new(20190214);
+  let mut second = SeededRandom::new(20190214);
+
+  for _ in 0..100 {
+    assert_eq!(first.next_u64(), second.next_u64());
+  }
+
+  let mut third = SeededRandom::new(20190215);
+
+  assert!(SeededRandom::new(20190214).next_u64() != third.next_u64());
+}
+
+#[test]
+fn test_next_in_range() {
+  let mut random = SeededRandom::new(4);
+
+  for _ in 0..1000 {
+    let val = random.next_in_range(-3, 5);
+    assert!(val >= -3 && val < 5);
+  }
+}
@@ -0,0 +1,14 @@
+//! Runs the tests of the binding generator in `build/gen`, which isn't
+//! compiled with the library since the build script is switched off
+
+#[macro_use] extern crate lazy_static;
+#[macro_use] extern crate handlebars;
+#[macro_use] extern crate serde;
+#[macro_use] extern crate derive_builder;
+
+extern crate heck;
+extern crate itertools;
+
+#[allow(dead_code)]
+#[path = "../build/gen/mod.rs"]
+mod gen;